@@ -1,3 +1,119 @@
-fn main() {
-    // Benchmarking code would go here
+use std::hint::black_box;
+
+use criterion::{
+    BatchSize, BenchmarkGroup, Criterion, criterion_group, criterion_main, measurement::WallTime,
+};
+use solver::{
+    SudokuSolver,
+    types::{Board, graph::Graph, masks::Masks},
+};
+
+/// Puzzles every phase is measured against
+struct Fixtures {
+    easy: Board<9>,
+    hard: Board<9>,
+    large: Board<16>,
 }
+
+fn fixtures() -> Fixtures {
+    Fixtures {
+        easy: Board::parse(include_str!("../dataset/input.txt")).unwrap(),
+        hard: Board::from_line(include_str!("fixtures/hard_9x9.txt")).unwrap(),
+        large: Board::from_line(include_str!("fixtures/unique_16x16.txt")).unwrap(),
+    }
+}
+
+/// Run `$bench::<N, K>` for every fixture inside a named group
+macro_rules! bench_fixtures {
+    ($c:expr, $group:literal, $bench:ident) => {{
+        let fixtures = fixtures();
+        let mut group = $c.benchmark_group($group);
+        $bench::<9, 3>(&mut group, "easy_9x9", &fixtures.easy);
+        $bench::<9, 3>(&mut group, "hard_9x9", &fixtures.hard);
+        $bench::<16, 4>(&mut group, "unique_16x16", &fixtures.large);
+        group.finish();
+    }};
+}
+
+fn masks<const N: usize, const K: usize>(
+    group: &mut BenchmarkGroup<WallTime>,
+    name: &str,
+    board: &Board<N>,
+) {
+    group.bench_function(name, |b| {
+        b.iter(|| {
+            let mut masks = Masks::<N>::default();
+            masks.generate(black_box(board)).unwrap();
+            masks
+        })
+    });
+}
+
+fn permutations<const N: usize, const K: usize>(
+    group: &mut BenchmarkGroup<WallTime>,
+    name: &str,
+    board: &Board<N>,
+) {
+    let solver = SudokuSolver::<N, K>::new(*board).unwrap();
+    group.bench_function(name, |b| {
+        b.iter(|| solver.generate_all_permutations(black_box(&solver.masks)))
+    });
+}
+
+fn edges<const N: usize, const K: usize>(
+    group: &mut BenchmarkGroup<WallTime>,
+    name: &str,
+    board: &Board<N>,
+) {
+    let solver = SudokuSolver::<N, K>::new(*board).unwrap();
+    group.bench_function(name, |b| {
+        b.iter_batched(
+            || solver.generate_all_permutations(&solver.masks),
+            |permutations| {
+                let mut graph = Graph::<K, N>::new(permutations);
+                graph.create_edges();
+                graph
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn solve<const N: usize, const K: usize>(
+    group: &mut BenchmarkGroup<WallTime>,
+    name: &str,
+    board: &Board<N>,
+) {
+    group.bench_function(name, |b| {
+        b.iter(|| {
+            SudokuSolver::<N, K>::new(black_box(*board))
+                .unwrap()
+                .solve()
+        })
+    });
+}
+
+fn bench_masks(c: &mut Criterion) {
+    bench_fixtures!(c, "masks_generate", masks);
+}
+
+fn bench_permutations(c: &mut Criterion) {
+    bench_fixtures!(c, "generate_all_permutations", permutations);
+}
+
+fn bench_edges(c: &mut Criterion) {
+    bench_fixtures!(c, "create_edges", edges);
+}
+
+fn bench_solve(c: &mut Criterion) {
+    bench_fixtures!(c, "solve", solve);
+}
+
+criterion_group!(
+    benches,
+    bench_masks,
+    bench_permutations,
+    bench_edges,
+    bench_solve
+);
+criterion_main!(benches);
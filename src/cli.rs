@@ -0,0 +1,209 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use log::LevelFilter;
+use solver::{
+    generator::Symmetry,
+    types::{Format, graph::PruneStrategy},
+};
+
+/// Minigrid-based graph constraint propagation solver for n×n Sudoku
+#[derive(Parser)]
+#[command(name = "sudoku_solver", version)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+
+    /// Board size N of an NxN puzzle (4, 9, 16, ..., 64), detected from the input when omitted
+    #[arg(short, long, global = true)]
+    pub size: Option<usize>,
+
+    /// Layout of the puzzle file
+    #[arg(long, global = true, value_enum, default_value_t = InputFormat::Grid)]
+    pub input_format: InputFormat,
+
+    /// Layout of the results printed to stdout
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    pub output_format: OutputFormat,
+
+    /// Fill naked and hidden singles before generating minigrid permutations
+    #[arg(long, global = true)]
+    pub propagate: bool,
+
+    /// X-Sudoku: both main diagonals must also hold every digit once
+    #[arg(long, global = true)]
+    pub diagonal: bool,
+
+    /// Jigsaw region map: one label per cell, N distinct labels (not for batch or generate)
+    #[arg(long, global = true)]
+    pub regions: Option<PathBuf>,
+
+    /// How the compatibility graph is pruned before extraction
+    #[arg(long, global = true, value_enum, default_value_t = Pruning::Degree)]
+    pub prune: Pruning,
+
+    /// Terminal log level (off, error, warn, info, debug, trace)
+    #[arg(long, global = true, default_value_t = LevelFilter::Info)]
+    pub log_level: LevelFilter,
+
+    /// Also write debug and trace records (every permutation, edge and search step) to this file
+    #[arg(long, global = true)]
+    pub trace_log: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Solve a puzzle and print every solution
+    Solve { file: PathBuf },
+    /// Print how many solutions a puzzle has
+    Count { file: PathBuf },
+    /// Check that a puzzle is well-formed and free of conflicts
+    Validate { file: PathBuf },
+    /// Rate the difficulty of a puzzle
+    Rate { file: PathBuf },
+    /// Show the easiest next move
+    Hint { file: PathBuf },
+    /// Print the pencil marks of a puzzle
+    Candidates {
+        file: PathBuf,
+        /// Narrow the marks with the logical techniques first
+        #[arg(long)]
+        logic: bool,
+    },
+    /// Check a partially filled copy of the puzzle against its solution
+    Check {
+        file: PathBuf,
+        /// The puzzle with the player's entries, in the same input format
+        progress: PathBuf,
+    },
+    /// Export the compatibility graph as JSON for visualization
+    Graph {
+        file: PathBuf,
+        /// Destination of the exported graph
+        #[arg(long, default_value = "graph.json")]
+        out: PathBuf,
+        /// Export the graph after pruning instead of the freshly built one
+        #[arg(long)]
+        pruned: bool,
+    },
+    /// Solve every puzzle of an SDM file (one single-line puzzle per line) in parallel
+    Batch {
+        file: PathBuf,
+        /// Write one result line per puzzle here instead of stdout
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Generate puzzles with a unique solution (size from --size, 9 by default)
+    Generate {
+        /// Number of puzzles to generate
+        #[arg(long, default_value_t = 1)]
+        count: usize,
+        /// Seed of the first puzzle; puzzle i uses seed + i
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Stop removing clues at this count instead of digging to a minimal puzzle
+        #[arg(long)]
+        clues: Option<usize>,
+        /// Cells removed together
+        #[arg(long, value_enum, default_value_t = SymmetryArg::None)]
+        symmetry: SymmetryArg,
+    },
+}
+
+impl Command {
+    /// Puzzle file to read, `None` for commands that do not read one
+    pub fn file(&self) -> Option<&PathBuf> {
+        match self {
+            Command::Solve { file }
+            | Command::Count { file }
+            | Command::Validate { file }
+            | Command::Rate { file }
+            | Command::Hint { file }
+            | Command::Candidates { file, .. }
+            | Command::Check { file, .. }
+            | Command::Graph { file, .. }
+            | Command::Batch { file, .. } => Some(file),
+            Command::Generate { .. } => None,
+        }
+    }
+
+    /// Name of the command when it cannot work with jigsaw regions
+    pub fn without_regions(&self) -> Option<&'static str> {
+        match self {
+            Command::Batch { .. } => Some("batch"),
+            Command::Generate { .. } => Some("generate"),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum SymmetryArg {
+    None,
+    /// 180° rotational symmetry
+    Rotational,
+    /// Left-right mirror symmetry
+    Mirror,
+}
+
+impl From<SymmetryArg> for Symmetry {
+    fn from(symmetry: SymmetryArg) -> Self {
+        match symmetry {
+            SymmetryArg::None => Symmetry::None,
+            SymmetryArg::Rotational => Symmetry::Rotational,
+            SymmetryArg::Mirror => Symmetry::Mirror,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum InputFormat {
+    /// Whitespace-separated integers, 0 for empty cells
+    Grid,
+    /// One character per cell on a single line ('.' or '0' for blanks)
+    Line,
+    /// Pipe-and-dash grid as printed by the solver
+    Pretty,
+    /// One line of characters per row
+    Sdk,
+}
+
+impl From<InputFormat> for Format {
+    fn from(format: InputFormat) -> Self {
+        match format {
+            InputFormat::Grid => Format::Grid,
+            InputFormat::Line => Format::Line,
+            InputFormat::Pretty => Format::Pretty,
+            InputFormat::Sdk => Format::Sdk,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Pruning {
+    /// Repeated sweeps removing nodes without a neighbour in every related minigrid
+    Degree,
+    /// AC-3 arc consistency over minigrid permutation domains
+    Ac3,
+    /// AC-3 plus complete K-tuples across every band and stack
+    Band,
+}
+
+impl From<Pruning> for PruneStrategy {
+    fn from(pruning: Pruning) -> Self {
+        match pruning {
+            Pruning::Degree => PruneStrategy::Degree,
+            Pruning::Ac3 => PruneStrategy::ArcConsistency,
+            Pruning::Band => PruneStrategy::BandConsistency,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Pretty grids
+    Text,
+    /// One single-line string per solution (N³ characters for pencil marks)
+    Line,
+    Json,
+}
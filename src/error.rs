@@ -0,0 +1,147 @@
+use std::fmt;
+
+/// Everything that can go wrong while ingesting or solving a puzzle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SudokuError {
+    /// Board dimension is not a perfect square, does not match the box size,
+    /// or has no solver instantiation
+    InvalidSize { size: usize },
+    /// A digit appears twice in the same row, column or box
+    Conflict {
+        row: usize,
+        col: usize,
+        box_idx: usize,
+        digit: u8,
+    },
+    /// A cell holds a digit greater than the board size
+    DigitOutOfRange {
+        row: usize,
+        col: usize,
+        digit: usize,
+        max: usize,
+    },
+    /// Input holds the wrong number of cells
+    CellCount { expected: usize, found: usize },
+    /// Cell count does not match any supported board size
+    UndetectableSize { cells: usize },
+    /// A token could not be read as a cell value
+    Parse {
+        row: usize,
+        col: usize,
+        token: String,
+    },
+    /// A region map does not use exactly N region ids
+    RegionCount { expected: usize, found: usize },
+    /// A region does not hold exactly N cells
+    RegionSize { region: usize, cells: usize },
+    /// A region's cells are not orthogonally connected
+    DisconnectedRegion { region: usize },
+    /// The requested command does not support irregular regions yet
+    RegionsUnsupported { command: String },
+    /// Reading or writing a file failed
+    Io { path: String, message: String },
+}
+
+impl fmt::Display for SudokuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SudokuError::InvalidSize { size } => {
+                write!(f, "unsupported board size {size}")
+            }
+            SudokuError::Conflict {
+                row,
+                col,
+                box_idx,
+                digit,
+            } => write!(
+                f,
+                "duplicate digit {digit} at row {row}, col {col} (box {box_idx})"
+            ),
+            SudokuError::DigitOutOfRange {
+                row,
+                col,
+                digit,
+                max,
+            } => write!(
+                f,
+                "digit {digit} at row {row}, col {col} is out of range 0..={max}"
+            ),
+            SudokuError::CellCount { expected, found } => {
+                write!(f, "expected {expected} cells, found {found}")
+            }
+            SudokuError::UndetectableSize { cells } => write!(
+                f,
+                "cannot detect board size from {cells} cells (expected N*N for N in 4, 9, ..., 64)"
+            ),
+            SudokuError::Parse { row, col, token } => {
+                write!(f, "invalid cell value {token:?} at row {row}, col {col}")
+            }
+            SudokuError::RegionCount { expected, found } => {
+                write!(f, "expected {expected} regions, found {found}")
+            }
+            SudokuError::RegionSize { region, cells } => {
+                write!(f, "region {region} has {cells} cells")
+            }
+            SudokuError::DisconnectedRegion { region } => {
+                write!(f, "region {region} is not connected")
+            }
+            SudokuError::RegionsUnsupported { command } => {
+                write!(f, "{command} does not support irregular regions")
+            }
+            SudokuError::Io { path, message } => write!(f, "{path}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for SudokuError {}
+
+#[cfg(test)]
+mod tests {
+    use super::SudokuError;
+    use crate::{SudokuSolver, types::Board};
+
+    #[test]
+    fn test_ingestion_errors_4x4() {
+        assert_eq!(
+            Board::<4>::parse("1 2 3 4\n0 0 0 0\n0 0 0 0"),
+            Err(SudokuError::CellCount {
+                expected: 16,
+                found: 12
+            })
+        );
+        assert_eq!(
+            Board::<4>::parse("1 2 3 4  0 x 0 0  0 0 0 0  0 0 0 0"),
+            Err(SudokuError::Parse {
+                row: 1,
+                col: 1,
+                token: "x".to_string()
+            })
+        );
+        assert_eq!(
+            Board::<4>::parse("1 2 3 4  0 0 5 0  0 0 0 0  0 0 0 0"),
+            Err(SudokuError::DigitOutOfRange {
+                row: 1,
+                col: 2,
+                digit: 5,
+                max: 4
+            })
+        );
+        assert_eq!(
+            Board::<3>::new([[0; 3]; 3]),
+            Err(SudokuError::InvalidSize { size: 3 })
+        );
+
+        let duplicate = Board::<4>::parse("1 0 0 0  0 0 0 0  0 0 0 0  0 0 0 1").unwrap();
+        assert!(SudokuSolver::<4, 2>::new(duplicate).is_ok());
+        let duplicate = Board::<4>::parse("1 0 0 0  0 0 0 0  0 0 0 0  1 0 0 0").unwrap();
+        assert_eq!(
+            SudokuSolver::<4, 2>::new(duplicate).err(),
+            Some(SudokuError::Conflict {
+                row: 3,
+                col: 0,
+                box_idx: 2,
+                digit: 1
+            })
+        );
+    }
+}
@@ -1,11 +1,61 @@
+use crate::types::masks::{DirtyMask, Word};
+
 pub struct BitMask<const N: usize>;
 
 impl<const N: usize> BitMask<N> {
-    pub const fn all_set() -> u32 {
-        (1 << N) - 1
+    pub const fn all_set() -> Word {
+        DirtyMask::<N>::FULL
     }
 
-    pub fn get(num: u8) -> u32 {
+    pub fn get(num: u8) -> Word {
         1 << (num - 1)
     }
 }
+
+/// Small seedable PRNG (SplitMix64), so generated puzzles are reproducible
+/// from their seed alone
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform-enough index in `0..n`
+    pub fn below(&mut self, n: usize) -> usize {
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    /// Fisher-Yates shuffle
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for idx in (1..items.len()).rev() {
+            items.swap(idx, self.below(idx + 1));
+        }
+    }
+}
+
+/// Complete NxN grid (N = K², K <= 8) in row-major order, built from a
+/// fixed pattern that also keeps both main diagonals free of repeats.
+///
+/// With r = K·a + b and c = K·p + q, the digit is K·((b + p) mod K) +
+/// ((a + p + q) mod K) + 1.
+pub(crate) fn pattern_values(n: usize) -> Vec<u8> {
+    let k = n.isqrt();
+    (0..n * n)
+        .map(|idx| {
+            let (r, c) = (idx / n, idx % n);
+            (k * ((r % k + c / k) % k) + (r / k + c / k + c % k) % k + 1) as u8
+        })
+        .collect()
+}
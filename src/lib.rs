@@ -1,8 +1,14 @@
+mod error;
 pub mod helper;
 mod log;
 mod solver;
 pub mod types;
-pub use solver::permutations;
+pub use solver::{batch, generator, permutations};
 
+pub use crate::error::SudokuError;
 pub use crate::log::init_logger;
-pub use crate::solver::SudokuSolver;
+pub use crate::solver::dynamic::{AnyBoard, AnySolveReport, AnySolver, solve_dyn};
+pub use crate::solver::{
+    Difficulty, Hint, Progress, Rating, SolveOutcome, SolveReport, SolveStats, SudokuSolver,
+    UnsolvableReason, check_progress, next_hint,
+};
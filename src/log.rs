@@ -1,23 +1,37 @@
+use std::path::Path;
+
 use fern::Dispatch;
 use log::LevelFilter;
 
-pub fn init_logger() {
-    Dispatch::new()
+use crate::SudokuError;
+
+/// Terminal output goes to stderr at `level`, so stdout stays free for results;
+/// with `trace_log`, debug and trace records also go to that file.
+///
+/// Fails when the trace log cannot be opened.
+pub fn init_logger(level: LevelFilter, trace_log: Option<&Path>) -> Result<(), SudokuError> {
+    let mut dispatch = Dispatch::new()
         // Terminal output with simpler format
         .chain(
             Dispatch::new()
                 .format(|out, message, record| {
                     out.finish(format_args!("[{}] {}", record.level(), message))
                 })
-                .level(LevelFilter::Info)
-                .chain(std::io::stdout()),
-        )
+                .level(level)
+                .chain(std::io::stderr()),
+        );
+
+    if let Some(path) = trace_log {
+        let file = fern::log_file(path).map_err(|err| SudokuError::Io {
+            path: path.display().to_string(),
+            message: err.to_string(),
+        })?;
         // File output with detailed format
-        .chain(
+        dispatch = dispatch.chain(
             Dispatch::new()
                 .format(|out, message, record| {
                     let file = record.file().unwrap_or("unknown");
-                    let filename = std::path::Path::new(file)
+                    let filename = Path::new(file)
                         .file_name()
                         .and_then(|n| n.to_str())
                         .unwrap_or(file);
@@ -35,8 +49,10 @@ pub fn init_logger() {
                 .filter(|metadata| {
                     metadata.level() == log::Level::Debug || metadata.level() == log::Level::Trace
                 })
-                .chain(fern::log_file("trace.log").expect("Failed to create log file")),
-        )
-        .apply()
-        .expect("Failed to initialize logger");
+                .chain(file),
+        );
+    }
+
+    dispatch.apply().expect("Failed to initialize logger");
+    Ok(())
 }
@@ -1,34 +1,302 @@
-use log::{debug, info};
-use solver::{SudokuSolver, init_logger, types};
+mod cli;
+
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Write},
+    path::Path,
+};
 
-const N: usize = 9;
-const K: usize = N.isqrt();
+use clap::Parser;
+use cli::{Cli, Command, OutputFormat};
+use log::{debug, info};
+use serde_json::json;
+use solver::{
+    AnyBoard, SudokuError, SudokuSolver, batch,
+    generator::{Generator, GeneratorConfig},
+    init_logger,
+    types::{Board, Regions, logic::LogicBoard},
+};
 
 fn main() {
-    init_logger();
+    let cli = Cli::parse();
+    if let Err(err) = init_logger(cli.log_level, cli.trace_log.as_deref()) {
+        fail(err);
+    }
     info!("Starting Sudoku Solver");
 
-    let content = std::fs::read_to_string("dataset/input.txt").expect("Failed to read input.txt");
-    info!("Read input file successfully");
+    if let (Some(_), Some(command)) = (&cli.regions, cli.command.without_regions()) {
+        fail(SudokuError::RegionsUnsupported {
+            command: command.to_string(),
+        });
+    }
+
+    let result = match (&cli.command, cli.command.file()) {
+        (Command::Batch { file, out }, _) => run_batch(&cli, file, out.as_deref()),
+        (_, Some(file)) => read_board(&cli, file).and_then(|board| dispatch(&cli, board)),
+        (_, None) => run_generate(&cli),
+    };
+
+    if let Err(err) = result {
+        fail(err);
+    }
+}
+
+fn fail(err: SudokuError) -> ! {
+    eprintln!("error: {}", err);
+    std::process::exit(1);
+}
+
+fn io_error(path: &Path) -> impl Fn(std::io::Error) -> SudokuError + '_ {
+    move |err| SudokuError::Io {
+        path: path.display().to_string(),
+        message: err.to_string(),
+    }
+}
+
+fn dispatch(cli: &Cli, board: AnyBoard) -> Result<(), SudokuError> {
+    match board {
+        AnyBoard::Size4(board) => run::<4, 2>(cli, board),
+        AnyBoard::Size9(board) => run::<9, 3>(cli, board),
+        AnyBoard::Size16(board) => run::<16, 4>(cli, *board),
+        AnyBoard::Size25(board) => run::<25, 5>(cli, *board),
+        AnyBoard::Size36(board) => run::<36, 6>(cli, *board),
+        AnyBoard::Size49(board) => run::<49, 7>(cli, *board),
+        AnyBoard::Size64(board) => run::<64, 8>(cli, *board),
+    }
+}
+
+/// Stream an SDM file through the parallel batch solver.
+///
+/// Results and a trailing `#` summary line go to `out` (or stdout).
+fn run_batch(cli: &Cli, file: &Path, out: Option<&Path>) -> Result<(), SudokuError> {
+    let reader = BufReader::new(File::open(file).map_err(io_error(file))?);
+    let mut writer: Box<dyn Write> = match out {
+        Some(path) => Box::new(BufWriter::new(File::create(path).map_err(io_error(path))?)),
+        None => Box::new(BufWriter::new(std::io::stdout().lock())),
+    };
+    let out_path = out.unwrap_or(Path::new("stdout"));
+    let json = cli.output_format == OutputFormat::Json;
+
+    let summary = batch::solve_batch(reader, cli.size, cli.diagonal, |result| {
+        if json {
+            writeln!(writer, "{}", json!(result))
+        } else {
+            writeln!(writer, "{}", result)
+        }
+        .map_err(io_error(out_path))
+    })?;
+
+    if json {
+        writeln!(writer, "{}", json!({ "summary": summary }))
+    } else {
+        writeln!(writer, "# {}", summary)
+    }
+    .and_then(|_| writer.flush())
+    .map_err(io_error(out_path))
+}
+
+/// Print generated puzzles in the requested output format
+fn run_generate(cli: &Cli) -> Result<(), SudokuError> {
+    match cli.size.unwrap_or(9) {
+        4 => generate::<4, 2>(cli),
+        9 => generate::<9, 3>(cli),
+        16 => generate::<16, 4>(cli),
+        25 => generate::<25, 5>(cli),
+        36 => generate::<36, 6>(cli),
+        49 => generate::<49, 7>(cli),
+        64 => generate::<64, 8>(cli),
+        size => Err(SudokuError::InvalidSize { size }),
+    }
+}
 
-    let mut cells = [[0u8; N]; N];
-    let mut nums = content
-        .split_whitespace()
-        .map(|s| s.parse::<u8>().expect("Invalid number"));
+fn generate<const N: usize, const K: usize>(cli: &Cli) -> Result<(), SudokuError> {
+    let Command::Generate {
+        count,
+        seed,
+        clues,
+        symmetry,
+    } = &cli.command
+    else {
+        unreachable!("only the generate command creates puzzles");
+    };
 
-    for row in cells.iter_mut() {
-        for cell in row.iter_mut() {
-            *cell = nums.next().expect("Not enough numbers in input file");
+    for idx in 0..*count {
+        let config = GeneratorConfig {
+            seed: seed.wrapping_add(idx as u64),
+            target_clues: *clues,
+            symmetry: (*symmetry).into(),
+            diagonal: cli.diagonal,
+        };
+        let puzzle = Generator::<N, K>::new(config).generate();
+        match cli.output_format {
+            OutputFormat::Text => println!("{}", puzzle),
+            OutputFormat::Line => println!("{}", puzzle.to_line()?),
+            OutputFormat::Json => println!(
+                "{}",
+                json!({ "seed": config.seed, "puzzle": rows(&puzzle) })
+            ),
         }
     }
+    Ok(())
+}
 
-    let board = types::Board::<N>::new(cells);
-    info!("Board created successfully");
+fn read_board(cli: &Cli, path: &Path) -> Result<AnyBoard, SudokuError> {
+    let content = std::fs::read_to_string(path).map_err(io_error(path))?;
+    info!("Read {} successfully", path.display());
+
+    let board = AnyBoard::parse_with(&content, cli.input_format.into(), cli.size)?;
+    info!("Detected {}x{} board", board.size(), board.size());
     debug!("Board state:\n{}", board);
+    Ok(board)
+}
 
-    let solver = SudokuSolver::<N, K>::new(board);
+fn rows<const N: usize>(board: &Board<N>) -> Vec<Vec<u8>> {
+    board.cells.iter().map(|row| row.to_vec()).collect()
+}
+
+fn run<const N: usize, const K: usize>(cli: &Cli, board: Board<N>) -> Result<(), SudokuError> {
+    let regions = match &cli.regions {
+        Some(path) => {
+            let content = std::fs::read_to_string(path).map_err(io_error(path))?;
+            Regions::parse(&content)?
+        }
+        None => Regions::default(),
+    };
+    let solver = SudokuSolver::<N, K>::new_with_regions(board, regions)?
+        .with_diagonals(cli.diagonal)?
+        .with_propagation(cli.propagate)
+        .with_prune_strategy(cli.prune.into());
     info!("Solver initialized");
 
-    solver.solve();
-    info!("Solving completed");
+    match &cli.command {
+        Command::Solve { .. } => {
+            let report = solver.solve();
+            let status = report.outcome.status();
+            match cli.output_format {
+                OutputFormat::Text => {
+                    println!(
+                        "{}: {} solution(s)",
+                        status,
+                        report.outcome.solution_count()
+                    );
+                    if let Some(reason) = report.outcome.reason() {
+                        println!("reason: {}", reason);
+                    }
+                    for solution in report.outcome.solutions() {
+                        println!("\n{}", solution);
+                    }
+                }
+                OutputFormat::Line => {
+                    for solution in report.outcome.solutions() {
+                        println!("{}", solution.to_line()?);
+                    }
+                }
+                OutputFormat::Json => {
+                    let solutions: Vec<_> = report.outcome.solutions().iter().map(rows).collect();
+                    let output = json!({
+                        "status": status,
+                        "reason": report.outcome.reason(),
+                        "solutions": solutions,
+                        "stats": report.stats,
+                    });
+                    println!("{}", output);
+                }
+            }
+        }
+        Command::Count { .. } => {
+            let count = solver.count_solutions(None);
+            match cli.output_format {
+                OutputFormat::Text | OutputFormat::Line => println!("{}", count),
+                // JSON numbers stop at u64, larger counts are written as strings
+                OutputFormat::Json => match u64::try_from(count) {
+                    Ok(count) => println!("{}", json!({ "count": count })),
+                    Err(_) => println!("{}", json!({ "count": count.to_string() })),
+                },
+            }
+        }
+        Command::Validate { .. } => {
+            // Parsing and mask generation already rejected malformed or conflicting input
+            match cli.output_format {
+                OutputFormat::Text | OutputFormat::Line => println!("valid"),
+                OutputFormat::Json => println!("{}", json!({ "valid": true })),
+            }
+        }
+        Command::Rate { .. } => {
+            let rating = solver.rate();
+            match cli.output_format {
+                OutputFormat::Text | OutputFormat::Line => {
+                    println!("{:.1} {}", rating.score, rating.difficulty)
+                }
+                OutputFormat::Json => println!("{}", json!(rating)),
+            }
+        }
+        Command::Hint { .. } => {
+            let hint = solver::next_hint::<N, K>(&board, &solver.masks)?;
+            match cli.output_format {
+                OutputFormat::Text | OutputFormat::Line => match hint {
+                    Some(hint) => println!("{}", hint),
+                    None => println!("no hint"),
+                },
+                OutputFormat::Json => {
+                    println!("{}", json!({ "hint": hint.map(|hint| hint.to_string()) }))
+                }
+            }
+        }
+        Command::Candidates { logic, .. } => {
+            let mut candidates = LogicBoard::new_with_rules(board, &solver.masks)?;
+            if *logic {
+                candidates.solve();
+            }
+            let candidates = candidates.candidate_grid();
+            match cli.output_format {
+                OutputFormat::Text => print!("{}", candidates),
+                OutputFormat::Line => println!("{}", candidates.to_line()?),
+                OutputFormat::Json => {
+                    println!("{}", json!({ "candidates": candidates.to_line()? }))
+                }
+            }
+        }
+        Command::Check { progress, .. } => {
+            let content = std::fs::read_to_string(progress).map_err(io_error(progress))?;
+            let entries = Board::<N>::parse_with(&content, cli.input_format.into())?;
+            let report = solver::check_progress::<N, K>(&board, &entries, &solver.masks)?;
+            match cli.output_format {
+                OutputFormat::Text | OutputFormat::Line => {
+                    let cells = |cells: &[(usize, usize)]| -> Vec<String> {
+                        cells
+                            .iter()
+                            .map(|(r, c)| format!("r{}c{}", r + 1, c + 1))
+                            .collect()
+                    };
+                    println!("solvable: {}", report.solvable);
+                    println!("complete: {}", report.complete);
+                    println!("wrong: {}", cells(&report.wrong).join(" "));
+                    println!("conflicts: {}", cells(&report.conflicts).join(" "));
+                }
+                OutputFormat::Json => println!("{}", json!(report)),
+            }
+        }
+        Command::Graph { out, pruned, .. } => {
+            let mut graph = solver.build_graph();
+            if *pruned {
+                graph.prune_with(cli.prune.into());
+            }
+            graph.export_to_json(&out.display().to_string())?;
+            match cli.output_format {
+                OutputFormat::Text | OutputFormat::Line => println!("{}", out.display()),
+                OutputFormat::Json => println!(
+                    "{}",
+                    json!({
+                        "out": out.display().to_string(),
+                        "vertices": graph.vertex_count(),
+                        "edges": graph.edge_count(),
+                    })
+                ),
+            }
+        }
+        Command::Batch { .. } | Command::Generate { .. } => {
+            unreachable!("batch and generate modes do not read a single board")
+        }
+    }
+    Ok(())
 }
@@ -0,0 +1,286 @@
+//! Batch solving of SDM puzzle collections (one single-line puzzle per line).
+//!
+//! Puzzles are streamed in fixed-size chunks and every chunk is solved in
+//! parallel, so memory stays bounded regardless of the file size.
+
+use std::{
+    fmt,
+    io::BufRead,
+    time::{Duration, Instant},
+};
+
+use log::{debug, info};
+use rayon::prelude::*;
+use serde::Serialize;
+
+use super::{
+    SolveStatus,
+    dynamic::{AnyBoard, AnySolver},
+};
+use crate::{
+    SudokuError,
+    types::{
+        Format,
+        format::{digit_to_char, is_comment},
+    },
+};
+
+/// Puzzles read from the input before a parallel solve round
+const CHUNK_SIZE: usize = 1024;
+
+/// Classification of a single puzzle in a batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchStatus {
+    Unsolvable,
+    Unique,
+    Multiple,
+    /// The puzzle could not be parsed or had conflicting givens
+    Error,
+}
+
+impl From<SolveStatus> for BatchStatus {
+    fn from(status: SolveStatus) -> Self {
+        match status {
+            SolveStatus::Unsolvable => BatchStatus::Unsolvable,
+            SolveStatus::Unique => BatchStatus::Unique,
+            SolveStatus::Multiple => BatchStatus::Multiple,
+        }
+    }
+}
+
+impl fmt::Display for BatchStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BatchStatus::Unsolvable => f.write_str("unsolvable"),
+            BatchStatus::Unique => f.write_str("unique"),
+            BatchStatus::Multiple => f.write_str("multiple"),
+            BatchStatus::Error => f.write_str("error"),
+        }
+    }
+}
+
+/// Result of a single puzzle in a batch
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchResult {
+    /// 1-based line number of the puzzle in the input
+    pub line: usize,
+    pub status: BatchStatus,
+    pub count: u128,
+    /// First solution in single-line form
+    pub solution: Option<String>,
+    pub elapsed_ms: f64,
+    pub error: Option<String>,
+}
+
+impl fmt::Display for BatchResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {:.3}ms",
+            self.line,
+            self.status,
+            self.count,
+            self.solution.as_deref().unwrap_or("-"),
+            self.elapsed_ms
+        )?;
+        if let Some(error) = &self.error {
+            write!(f, " {}", error)?;
+        }
+        Ok(())
+    }
+}
+
+/// Totals over a whole batch
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BatchSummary {
+    pub total: usize,
+    pub unique: usize,
+    pub ambiguous: usize,
+    pub unsolvable: usize,
+    /// Puzzles that could not be parsed or had conflicting givens
+    pub failures: usize,
+    pub elapsed_ms: f64,
+    /// Puzzles per second, wall clock
+    pub throughput: f64,
+}
+
+impl BatchSummary {
+    fn record(&mut self, result: &BatchResult) {
+        self.total += 1;
+        match result.status {
+            BatchStatus::Unique => self.unique += 1,
+            BatchStatus::Multiple => self.ambiguous += 1,
+            BatchStatus::Unsolvable => self.unsolvable += 1,
+            BatchStatus::Error => self.failures += 1,
+        }
+    }
+}
+
+impl fmt::Display for BatchSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "total={} unique={} ambiguous={} unsolvable={} failures={} elapsed={:.3}ms throughput={:.1}/s",
+            self.total,
+            self.unique,
+            self.ambiguous,
+            self.unsolvable,
+            self.failures,
+            self.elapsed_ms,
+            self.throughput
+        )
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Solve one single-line puzzle, detecting its size unless given.
+///
+/// With `diagonal`, the puzzle is an X-Sudoku.
+pub fn solve_line(line_no: usize, line: &str, size: Option<usize>, diagonal: bool) -> BatchResult {
+    let start = Instant::now();
+    // Count on the graph and stream only the first solution, so an ambiguous
+    // line never materializes all of its solutions
+    let result = AnyBoard::parse_with(line, Format::Line, size)
+        .and_then(AnySolver::new)
+        .and_then(|solver| solver.with_diagonals(diagonal))
+        .map(|solver| {
+            let count = solver.count_solutions(None);
+            let first = if count > 0 {
+                solver.first_solution()
+            } else {
+                None
+            };
+            (count, first)
+        });
+
+    let elapsed_ms = millis(start.elapsed());
+    match result {
+        Ok((count, first)) => {
+            let solution = first.map(|values| {
+                values
+                    .iter()
+                    .filter_map(|&digit| digit_to_char(digit))
+                    .collect()
+            });
+            BatchResult {
+                line: line_no,
+                status: SolveStatus::from_count(count).into(),
+                count,
+                solution,
+                elapsed_ms,
+                error: None,
+            }
+        }
+        Err(err) => BatchResult {
+            line: line_no,
+            status: BatchStatus::Error,
+            count: 0,
+            solution: None,
+            elapsed_ms,
+            error: Some(err.to_string()),
+        },
+    }
+}
+
+/// Stream puzzles from `reader`, solve them in parallel and hand every
+/// result to `emit` in input order.
+///
+/// Blank lines and `#` comments are skipped. With `diagonal`, every puzzle is
+/// an X-Sudoku.
+pub fn solve_batch<R, F>(
+    reader: R,
+    size: Option<usize>,
+    diagonal: bool,
+    mut emit: F,
+) -> Result<BatchSummary, SudokuError>
+where
+    R: BufRead,
+    F: FnMut(&BatchResult) -> Result<(), SudokuError>,
+{
+    let start = Instant::now();
+    let mut summary = BatchSummary::default();
+    let mut lines = reader.lines().enumerate();
+
+    loop {
+        let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+        for (idx, line) in lines.by_ref() {
+            let line = line.map_err(|err| SudokuError::Io {
+                path: format!("line {}", idx + 1),
+                message: err.to_string(),
+            })?;
+            if is_comment(&line) {
+                continue;
+            }
+            chunk.push((idx + 1, line.trim().to_string()));
+            if chunk.len() == CHUNK_SIZE {
+                break;
+            }
+        }
+        if chunk.is_empty() {
+            break;
+        }
+
+        debug!("Solving chunk of {} puzzle(s)", chunk.len());
+        let results: Vec<BatchResult> = chunk
+            .par_iter()
+            .map(|(line_no, line)| solve_line(*line_no, line, size, diagonal))
+            .collect();
+
+        for result in &results {
+            summary.record(result);
+            emit(result)?;
+        }
+    }
+
+    let elapsed = start.elapsed();
+    summary.elapsed_ms = millis(elapsed);
+    summary.throughput = summary.total as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+    info!("Batch finished: {}", summary);
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Board;
+
+    #[test]
+    fn test_batch_counts() {
+        let input = "\
+# mixed corpus
+745.9.....3215..46...28.5.32......6.98.6..351...54.2.73.8.....2.2.76..1..6.9.8.34
+................
+1...............
+77..............
+..12....3.......4...............
+";
+        let mut lines = Vec::new();
+        let summary = solve_batch(input.as_bytes(), None, false, |result| {
+            lines.push(result.to_string());
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(summary.total, 5);
+        assert_eq!(summary.unique, 1);
+        assert_eq!(summary.ambiguous, 2);
+        assert_eq!(summary.failures, 2);
+        assert!(lines[0].starts_with(
+            "2 unique 1 745396128832157946196284573257831469984672351613549287378415692429763815561928734 "
+        ));
+        assert!(lines[1].starts_with("3 multiple 288 "));
+        // Only the first of the 288 solutions is built
+        let first = lines[1].split(' ').nth(3).unwrap();
+        assert!(Board::<4>::from_line(first).unwrap().is_valid());
+        assert!(!first.contains('.'));
+        assert!(lines[3].starts_with("5 error 0 - "));
+        assert_eq!(
+            serde_json::to_value(BatchStatus::Error).unwrap(),
+            serde_json::json!("error")
+        );
+    }
+}
@@ -0,0 +1,340 @@
+//! Runtime front door over the const-generic solver: the board size is
+//! detected from the input and routed to the matching `SudokuSolver<N, K>`.
+
+use std::fmt;
+
+use log::debug;
+
+use super::{SolveReport, SolveStats, SolveStatus, SudokuSolver, UnsolvableReason};
+use crate::{
+    SudokuError,
+    types::{Board, Format, format::read_values},
+};
+
+/// Board sizes with a monomorphized solver
+pub const SUPPORTED_SIZES: [usize; 7] = [4, 9, 16, 25, 36, 49, 64];
+
+/// Apply the same expression to whichever size variant `$value` holds
+macro_rules! dispatch {
+    ($ty:ident, $value:expr, $inner:ident => $body:expr) => {
+        match $value {
+            $ty::Size4($inner) => $body,
+            $ty::Size9($inner) => $body,
+            $ty::Size16($inner) => $body,
+            $ty::Size25($inner) => $body,
+            $ty::Size36($inner) => $body,
+            $ty::Size49($inner) => $body,
+            $ty::Size64($inner) => $body,
+        }
+    };
+}
+
+/// A board whose size is only known at runtime
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnyBoard {
+    Size4(Board<4>),
+    Size9(Board<9>),
+    Size16(Box<Board<16>>),
+    Size25(Box<Board<25>>),
+    Size36(Box<Board<36>>),
+    Size49(Box<Board<49>>),
+    Size64(Box<Board<64>>),
+}
+
+impl AnyBoard {
+    /// Build a board from row-major cell values (0 = empty).
+    ///
+    /// Without an explicit `size`, it is detected from the cell count
+    /// (16, 81, 256, 625, ... up to 4096 cells).
+    pub fn from_cells(cells: &[u8], size: Option<usize>) -> Result<Self, SudokuError> {
+        let size = match size {
+            Some(size) => size,
+            None => detect_size(cells.len())?,
+        };
+        debug!(
+            "Building {}x{} board from {} cells",
+            size,
+            size,
+            cells.len()
+        );
+
+        Ok(match size {
+            4 => AnyBoard::Size4(Board::from_slice(cells)?),
+            9 => AnyBoard::Size9(Board::from_slice(cells)?),
+            16 => AnyBoard::Size16(Box::new(Board::from_slice(cells)?)),
+            25 => AnyBoard::Size25(Box::new(Board::from_slice(cells)?)),
+            36 => AnyBoard::Size36(Box::new(Board::from_slice(cells)?)),
+            49 => AnyBoard::Size49(Box::new(Board::from_slice(cells)?)),
+            64 => AnyBoard::Size64(Box::new(Board::from_slice(cells)?)),
+            size => return Err(SudokuError::InvalidSize { size }),
+        })
+    }
+
+    /// Parse whitespace-separated cell values, detecting the size when not given
+    pub fn parse(input: &str, size: Option<usize>) -> Result<Self, SudokuError> {
+        Self::parse_with(input, Format::Grid, size)
+    }
+
+    pub fn parse_with(
+        input: &str,
+        format: Format,
+        size: Option<usize>,
+    ) -> Result<Self, SudokuError> {
+        let (size, values) = read_values(input, format, size)?;
+        Self::from_cells(&values, Some(size))
+    }
+
+    pub fn to_format(&self, format: Format) -> Result<String, SudokuError> {
+        dispatch!(AnyBoard, self, board => board.to_format(format))
+    }
+
+    pub fn size(&self) -> usize {
+        match self {
+            AnyBoard::Size4(_) => 4,
+            AnyBoard::Size9(_) => 9,
+            AnyBoard::Size16(_) => 16,
+            AnyBoard::Size25(_) => 25,
+            AnyBoard::Size36(_) => 36,
+            AnyBoard::Size49(_) => 49,
+            AnyBoard::Size64(_) => 64,
+        }
+    }
+
+    /// Row-major cell values (0 = empty)
+    pub fn values(&self) -> Vec<u8> {
+        dispatch!(AnyBoard, self, board => board.values())
+    }
+
+    pub fn is_valid(&self) -> bool {
+        dispatch!(AnyBoard, self, board => board.is_valid())
+    }
+}
+
+impl fmt::Display for AnyBoard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        dispatch!(AnyBoard, self, board => board.fmt(f))
+    }
+}
+
+/// Map a cell count to the board size it belongs to
+pub fn detect_size(cell_count: usize) -> Result<usize, SudokuError> {
+    SUPPORTED_SIZES
+        .into_iter()
+        .find(|size| size * size == cell_count)
+        .ok_or(SudokuError::UndetectableSize { cells: cell_count })
+}
+
+/// A solver whose board size is only known at runtime
+pub enum AnySolver {
+    Size4(Box<SudokuSolver<4, 2>>),
+    Size9(Box<SudokuSolver<9, 3>>),
+    Size16(Box<SudokuSolver<16, 4>>),
+    Size25(Box<SudokuSolver<25, 5>>),
+    Size36(Box<SudokuSolver<36, 6>>),
+    Size49(Box<SudokuSolver<49, 7>>),
+    Size64(Box<SudokuSolver<64, 8>>),
+}
+
+impl AnySolver {
+    pub fn new(board: AnyBoard) -> Result<Self, SudokuError> {
+        Ok(match board {
+            AnyBoard::Size4(board) => AnySolver::Size4(Box::new(SudokuSolver::new(board)?)),
+            AnyBoard::Size9(board) => AnySolver::Size9(Box::new(SudokuSolver::new(board)?)),
+            AnyBoard::Size16(board) => AnySolver::Size16(Box::new(SudokuSolver::new(*board)?)),
+            AnyBoard::Size25(board) => AnySolver::Size25(Box::new(SudokuSolver::new(*board)?)),
+            AnyBoard::Size36(board) => AnySolver::Size36(Box::new(SudokuSolver::new(*board)?)),
+            AnyBoard::Size49(board) => AnySolver::Size49(Box::new(SudokuSolver::new(*board)?)),
+            AnyBoard::Size64(board) => AnySolver::Size64(Box::new(SudokuSolver::new(*board)?)),
+        })
+    }
+
+    /// Also require both main diagonals to hold every digit once (X-Sudoku)
+    pub fn with_diagonals(self, enabled: bool) -> Result<Self, SudokuError> {
+        Ok(match self {
+            AnySolver::Size4(solver) => AnySolver::Size4(Box::new(solver.with_diagonals(enabled)?)),
+            AnySolver::Size9(solver) => AnySolver::Size9(Box::new(solver.with_diagonals(enabled)?)),
+            AnySolver::Size16(solver) => {
+                AnySolver::Size16(Box::new(solver.with_diagonals(enabled)?))
+            }
+            AnySolver::Size25(solver) => {
+                AnySolver::Size25(Box::new(solver.with_diagonals(enabled)?))
+            }
+            AnySolver::Size36(solver) => {
+                AnySolver::Size36(Box::new(solver.with_diagonals(enabled)?))
+            }
+            AnySolver::Size49(solver) => {
+                AnySolver::Size49(Box::new(solver.with_diagonals(enabled)?))
+            }
+            AnySolver::Size64(solver) => {
+                AnySolver::Size64(Box::new(solver.with_diagonals(enabled)?))
+            }
+        })
+    }
+
+    /// See [`SudokuSolver::count_solutions`]
+    pub fn count_solutions(&self, limit: Option<u128>) -> u128 {
+        dispatch!(AnySolver, self, solver => solver.count_solutions(limit))
+    }
+
+    /// First solution as row-major cell values, without collecting the rest
+    pub fn first_solution(&self) -> Option<Vec<u8>> {
+        dispatch!(AnySolver, self, solver => solver.solutions().next().map(|board| board.values()))
+    }
+
+    pub fn solve(&self) -> AnySolveReport {
+        match self {
+            AnySolver::Size4(solver) => AnySolveReport::Size4(solver.solve()),
+            AnySolver::Size9(solver) => AnySolveReport::Size9(solver.solve()),
+            AnySolver::Size16(solver) => AnySolveReport::Size16(Box::new(solver.solve())),
+            AnySolver::Size25(solver) => AnySolveReport::Size25(Box::new(solver.solve())),
+            AnySolver::Size36(solver) => AnySolveReport::Size36(Box::new(solver.solve())),
+            AnySolver::Size49(solver) => AnySolveReport::Size49(Box::new(solver.solve())),
+            AnySolver::Size64(solver) => AnySolveReport::Size64(Box::new(solver.solve())),
+        }
+    }
+}
+
+/// `SolveReport` of whichever size was solved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnySolveReport {
+    Size4(SolveReport<4>),
+    Size9(SolveReport<9>),
+    Size16(Box<SolveReport<16>>),
+    Size25(Box<SolveReport<25>>),
+    Size36(Box<SolveReport<36>>),
+    Size49(Box<SolveReport<49>>),
+    Size64(Box<SolveReport<64>>),
+}
+
+impl AnySolveReport {
+    pub fn size(&self) -> usize {
+        match self {
+            AnySolveReport::Size4(_) => 4,
+            AnySolveReport::Size9(_) => 9,
+            AnySolveReport::Size16(_) => 16,
+            AnySolveReport::Size25(_) => 25,
+            AnySolveReport::Size36(_) => 36,
+            AnySolveReport::Size49(_) => 49,
+            AnySolveReport::Size64(_) => 64,
+        }
+    }
+
+    pub fn status(&self) -> SolveStatus {
+        dispatch!(AnySolveReport, self, report => report.outcome.status())
+    }
+
+    pub fn solution_count(&self) -> usize {
+        dispatch!(AnySolveReport, self, report => report.outcome.solution_count())
+    }
+
+    pub fn reason(&self) -> Option<UnsolvableReason> {
+        dispatch!(AnySolveReport, self, report => report.outcome.reason())
+    }
+
+    pub fn stats(&self) -> &SolveStats {
+        dispatch!(AnySolveReport, self, report => &report.stats)
+    }
+
+    /// Every solution as row-major cell values
+    pub fn solutions(&self) -> Vec<Vec<u8>> {
+        dispatch!(AnySolveReport, self, report => {
+            report.outcome.solutions().iter().map(Board::values).collect()
+        })
+    }
+}
+
+/// Solve row-major cell values of any supported size in one call.
+///
+/// `size` may be omitted to detect it from the number of cells.
+pub fn solve_dyn(cells: &[u8], size: Option<usize>) -> Result<AnySolveReport, SudokuError> {
+    let board = AnyBoard::from_cells(cells, size)?;
+    Ok(AnySolver::new(board)?.solve())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        helper::pattern_values,
+        types::masks::{DirtyMask, Masks},
+    };
+
+    #[test]
+    fn test_size_detection() {
+        assert_eq!(detect_size(16), Ok(4));
+        assert_eq!(detect_size(81), Ok(9));
+        assert_eq!(detect_size(256), Ok(16));
+        assert_eq!(detect_size(625), Ok(25));
+        assert_eq!(detect_size(1296), Ok(36));
+        assert_eq!(detect_size(4096), Ok(64));
+        assert!(detect_size(80).is_err());
+
+        let report = solve_dyn(&[0; 16], None).unwrap();
+        assert_eq!(report.size(), 4);
+        assert_eq!(report.solution_count(), 288);
+
+        let board = AnyBoard::parse(
+            "7 4 5 0 9 0 0 0 0  0 3 2 1 5 0 0 4 6  0 0 0 2 8 0 5 0 3
+             2 0 0 0 0 0 0 6 0  9 8 0 6 0 0 3 5 1  0 0 0 5 4 0 2 0 7
+             3 0 8 0 0 0 0 0 2  0 2 0 7 6 0 0 1 0  0 6 0 9 0 8 0 3 4",
+            None,
+        )
+        .unwrap();
+        assert_eq!(board.size(), 9);
+        let report = AnySolver::new(board).unwrap().solve();
+        assert_eq!(report.status(), SolveStatus::Unique);
+        assert_eq!(report.solutions()[0][..9], [7, 4, 5, 3, 9, 6, 1, 2, 8]);
+    }
+
+    #[test]
+    fn test_largest_sizes() {
+        for n in [49, 64] {
+            let mut cells = pattern_values(n);
+            cells[n + 3] = 0;
+            let grid = AnyBoard::from_cells(&cells, None)
+                .unwrap()
+                .to_format(Format::Grid)
+                .unwrap();
+            let board = AnyBoard::parse(&grid, None).unwrap();
+            assert_eq!(board.size(), n);
+            assert_eq!(board.values(), cells);
+            assert!(board.is_valid());
+            let pretty = board.to_format(Format::Pretty).unwrap();
+            assert_eq!(
+                AnyBoard::parse_with(&pretty, Format::Pretty, None),
+                Ok(board)
+            );
+        }
+
+        // Digit 64 lives in the top bit of `Word`
+        let solved = Board::<64>::from_slice(&pattern_values(64)).unwrap();
+        let mut masks = Masks::<64>::default();
+        masks.generate(&solved).unwrap();
+        assert!(
+            masks
+                .rows
+                .iter()
+                .chain(&masks.cols)
+                .chain(&masks.boxs)
+                .all(DirtyMask::is_all_set)
+        );
+        assert!(masks.conflict.iter().flatten().all(DirtyMask::is_all_set));
+
+        let mut board = solved;
+        board.cells[0][63] = 0;
+        board.cells[10][20] = 0;
+        let digit = solved.cells[0][63] as usize;
+        let mut masks = Masks::<64>::default();
+        masks.generate(&board).unwrap();
+        assert!(!masks.rows[0].is_dirty(digit) && masks.rows[1].is_dirty(digit));
+        assert_eq!(*masks.conflict[0][63].get(), !(1 << (digit - 1)));
+
+        let mut duplicate = solved;
+        duplicate.cells[0][0] = duplicate.cells[0][1];
+        assert!(Masks::<64>::default().generate(&duplicate).is_err());
+
+        let report = solve_dyn(&board.values(), None).unwrap();
+        assert_eq!(report.size(), 64);
+        assert_eq!(report.solutions(), vec![solved.values()]);
+    }
+}
@@ -0,0 +1,394 @@
+//! Puzzle generation: a random complete grid, then clue removal while the
+//! solver still proves the puzzle unique.
+//!
+//! Everything is driven by a seeded `SplitMix64`, so the same configuration
+//! always yields the same puzzle.
+
+use log::{debug, info};
+
+use super::SudokuSolver;
+use crate::{
+    helper::{BitMask, SplitMix64, pattern_values},
+    types::{
+        Board,
+        logic::digits,
+        masks::{DirtyMask, Masks, Word},
+    },
+};
+
+/// Which cells are removed together
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    #[default]
+    None,
+    /// 180° rotation: (r, c) pairs with (N-1-r, N-1-c)
+    Rotational,
+    /// Left-right mirror: (r, c) pairs with (r, N-1-c)
+    Mirror,
+}
+
+impl Symmetry {
+    /// Cells removed together with (r, c), including itself
+    fn group<const N: usize>(self, r: usize, c: usize) -> Vec<(usize, usize)> {
+        let partner = match self {
+            Symmetry::None => return vec![(r, c)],
+            Symmetry::Rotational => (N - 1 - r, N - 1 - c),
+            Symmetry::Mirror => (r, N - 1 - c),
+        };
+        if partner == (r, c) {
+            vec![(r, c)]
+        } else {
+            vec![(r, c), partner]
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct GeneratorConfig {
+    pub seed: u64,
+    /// Stop removing once this many clues are left; `None` removes as many as
+    /// possible, which yields a minimal puzzle
+    pub target_clues: Option<usize>,
+    pub symmetry: Symmetry,
+    /// X-Sudoku: both main diagonals also hold every digit once
+    pub diagonal: bool,
+}
+
+pub struct Generator<const N: usize, const K: usize> {
+    config: GeneratorConfig,
+    rng: SplitMix64,
+}
+
+impl<const N: usize, const K: usize> Generator<N, K> {
+    pub fn new(config: GeneratorConfig) -> Self {
+        Self {
+            rng: SplitMix64::new(config.seed),
+            config,
+        }
+    }
+
+    /// A random permutation of `0..count`
+    fn permutation(&mut self, count: usize) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..count).collect();
+        self.rng.shuffle(&mut order);
+        order
+    }
+
+    /// Random row (or column) order that keeps every band (or stack) together
+    fn line_order(&mut self) -> Vec<usize> {
+        let bands = self.permutation(K);
+        bands
+            .into_iter()
+            .flat_map(|band| {
+                self.permutation(K)
+                    .into_iter()
+                    .map(move |line| band * K + line)
+            })
+            .collect()
+    }
+
+    /// Placements a single fill attempt may try before it restarts; random
+    /// fills of larger boards occasionally wander into very deep dead ends
+    const FILL_BUDGET: usize = 8 * N * N;
+
+    /// Largest size filled by backtracking: beyond it fills rarely finish
+    /// within their budget
+    const MAX_FILL_SIZE: usize = 25;
+
+    /// Fill attempts before falling back to the pattern grid
+    const FILL_ATTEMPTS: usize = 16;
+
+    /// Fill every empty cell by randomized backtracking: the cell with the
+    /// fewest candidates first, its candidates tried in random order.
+    ///
+    /// `masks` hold the digits already placed per unit. Gives up once
+    /// `budget` placements have been tried.
+    fn fill(&mut self, board: &mut Board<N>, masks: &mut Masks<N>, budget: &mut usize) -> bool {
+        let mut best: Option<(usize, usize, Word)> = None;
+        for (r, row) in board.cells.iter().enumerate() {
+            for (c, _) in row.iter().enumerate().filter(|&(_, &value)| value == 0) {
+                let free = !Self::placed(masks, r, c) & BitMask::<N>::all_set();
+                if best.is_none_or(|(_, _, other)| free.count_ones() < other.count_ones()) {
+                    best = Some((r, c, free));
+                }
+            }
+        }
+        let Some((r, c, free)) = best else {
+            return true;
+        };
+
+        let mut candidates: Vec<u8> = digits(free).collect();
+        self.rng.shuffle(&mut candidates);
+        for digit in candidates {
+            if *budget == 0 {
+                break;
+            }
+            *budget -= 1;
+            board.cells[r][c] = digit;
+            Self::mark(masks, r, c, digit as usize, true);
+            if self.fill(board, masks, budget) {
+                return true;
+            }
+            Self::mark(masks, r, c, digit as usize, false);
+        }
+        board.cells[r][c] = 0;
+        false
+    }
+
+    /// Digits already placed in the units of (r, c)
+    fn placed(masks: &Masks<N>, r: usize, c: usize) -> Word {
+        let mut placed = masks.rows[r] | masks.cols[c] | masks.boxs[Board::<N>::box_idx(r, c)];
+        for d in masks.cell_diagonals(r, c) {
+            placed |= masks.diags[d];
+        }
+        *placed.get()
+    }
+
+    /// Record `digit` in the units of (r, c), or forget it again
+    fn mark(masks: &mut Masks<N>, r: usize, c: usize, digit: usize, placed: bool) {
+        let toggle = |mask: &mut DirtyMask<N>| {
+            if placed {
+                mask.dirty_set(digit);
+            } else {
+                mask.reset(digit - 1);
+            }
+        };
+        toggle(&mut masks.rows[r]);
+        toggle(&mut masks.cols[c]);
+        toggle(&mut masks.boxs[Board::<N>::box_idx(r, c)]);
+        for d in masks.cell_diagonals(r, c) {
+            toggle(&mut masks.diags[d]);
+        }
+    }
+
+    /// Complete grid to shuffle: a randomized backtracking fill, or the
+    /// pattern grid for boards above `MAX_FILL_SIZE` and fills that keep
+    /// running out of budget
+    fn base_grid(&mut self) -> Board<N> {
+        if N <= Self::MAX_FILL_SIZE {
+            for _ in 0..Self::FILL_ATTEMPTS {
+                let mut filled = Board {
+                    cells: [[0u8; N]; N],
+                };
+                let mut masks = Masks {
+                    diagonal: self.config.diagonal,
+                    ..Default::default()
+                };
+                let mut budget = Self::FILL_BUDGET;
+                if self.fill(&mut filled, &mut masks, &mut budget) {
+                    return filled;
+                }
+                debug!("Grid fill ran out of budget, restarting");
+            }
+        }
+        debug!("Starting from the {}x{} pattern grid", N, N);
+        Board::from_slice(&pattern_values(N)).expect("the pattern grid has N*N cells")
+    }
+
+    /// A random complete grid: `base_grid` with shuffled digits and an
+    /// optional transpose on top, plus shuffled rows within bands, bands,
+    /// columns within stacks and stacks unless the diagonals must hold
+    /// (those moves would break them)
+    pub fn complete_grid(&mut self) -> Board<N> {
+        let filled = self.base_grid();
+
+        let digits = self.permutation(N);
+        let (rows, cols) = if self.config.diagonal {
+            ((0..N).collect(), (0..N).collect())
+        } else {
+            (self.line_order(), self.line_order())
+        };
+        let transpose = self.rng.below(2) == 1;
+
+        let mut board = Board {
+            cells: [[0u8; N]; N],
+        };
+        for (r, &pr) in rows.iter().enumerate() {
+            for (c, &pc) in cols.iter().enumerate() {
+                let (pr, pc) = if transpose { (pc, pr) } else { (pr, pc) };
+                board.cells[r][c] = digits[filled.cells[pr][pc] as usize - 1] as u8 + 1;
+            }
+        }
+        debug_assert!(board.is_valid());
+        board
+    }
+
+    fn is_unique(&self, board: Board<N>) -> bool {
+        SudokuSolver::<N, K>::new(board)
+            .and_then(|solver| solver.with_diagonals(self.config.diagonal))
+            .map(|solver| solver.with_propagation(true).is_unique())
+            .unwrap_or(false)
+    }
+
+    /// Remove clues from `grid` in random order, keeping every removal that
+    /// leaves the puzzle unique, until the target clue count is reached
+    pub fn dig(&mut self, grid: Board<N>) -> Board<N> {
+        let target = self.config.target_clues.unwrap_or(0);
+        let mut puzzle = grid;
+        let mut clues = N * N;
+
+        let mut cells: Vec<(usize, usize)> =
+            (0..N).flat_map(|r| (0..N).map(move |c| (r, c))).collect();
+        self.rng.shuffle(&mut cells);
+
+        for (r, c) in cells {
+            if clues <= target {
+                break;
+            }
+            if puzzle.cells[r][c] == 0 {
+                continue;
+            }
+            let group = self.config.symmetry.group::<N>(r, c);
+            if clues - group.len() < target {
+                continue;
+            }
+
+            let mut candidate = puzzle;
+            for &(gr, gc) in &group {
+                candidate.cells[gr][gc] = 0;
+            }
+            if self.is_unique(candidate) {
+                puzzle = candidate;
+                clues -= group.len();
+                debug!("Removed {:?}, {} clue(s) left", group, clues);
+            }
+        }
+        info!("Generated puzzle with {} clue(s)", clues);
+        puzzle
+    }
+
+    /// A new puzzle with a unique solution
+    pub fn generate(&mut self) -> Board<N> {
+        let grid = self.complete_grid();
+        self.dig(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clues<const N: usize>(board: &Board<N>) -> usize {
+        board.values().iter().filter(|&&value| value != 0).count()
+    }
+
+    /// Digit sets of the three row segments of a box, sorted
+    fn row_segments(grid: &Board<9>, band: usize, stack: usize) -> Vec<Word> {
+        let mut segments: Vec<Word> = (0..3)
+            .map(|i| {
+                (0..3)
+                    .map(|j| BitMask::<9>::get(grid.cells[band * 3 + i][stack * 3 + j]))
+                    .fold(0, |acc, bit| acc | bit)
+            })
+            .collect();
+        segments.sort_unstable();
+        segments
+    }
+
+    #[test]
+    fn test_complete_grids_are_not_one_pattern() {
+        // Every grid of the pattern class repeats the same row segments in all
+        // boxes of a band; a random fill almost never does
+        let pattern_like = |grid: &Board<9>| {
+            (0..3).all(|band| {
+                (1..3).all(|stack| row_segments(grid, band, stack) == row_segments(grid, band, 0))
+            })
+        };
+        let grids: Vec<Board<9>> = (0..4)
+            .map(|seed| {
+                Generator::<9, 3>::new(GeneratorConfig {
+                    seed,
+                    ..Default::default()
+                })
+                .complete_grid()
+            })
+            .collect();
+        assert!(grids.iter().all(Board::is_valid));
+        assert!(grids.iter().all(|grid| !pattern_like(grid)));
+        assert!(grids.windows(2).all(|pair| pair[0] != pair[1]));
+    }
+
+    #[test]
+    fn test_complete_grid_large_sizes() {
+        fn assert_complete<const N: usize, const K: usize>(diagonal: bool) {
+            let config = GeneratorConfig {
+                diagonal,
+                ..Default::default()
+            };
+            let grid = Generator::<N, K>::new(config).complete_grid();
+            assert!(grid.values().iter().all(|&digit| digit != 0));
+            let rules = Masks {
+                diagonal,
+                ..Default::default()
+            };
+            assert!(rules.conflicting_cells(&grid).is_empty());
+        }
+        assert_complete::<36, 6>(false);
+        assert_complete::<49, 7>(false);
+        assert_complete::<64, 8>(false);
+        assert_complete::<64, 8>(true);
+    }
+
+    #[test]
+    fn test_generate_is_seeded_and_unique() {
+        let config = GeneratorConfig {
+            seed: 7,
+            target_clues: Some(30),
+            symmetry: Symmetry::Rotational,
+            diagonal: false,
+        };
+        let mut generator = Generator::<9, 3>::new(config);
+        let puzzle = generator.generate();
+        assert_eq!(puzzle, Generator::<9, 3>::new(config).generate());
+        assert!(generator.is_unique(puzzle));
+        assert!(clues(&puzzle) >= 30);
+
+        for r in 0..9 {
+            for c in 0..9 {
+                assert_eq!(puzzle.cells[r][c] == 0, puzzle.cells[8 - r][8 - c] == 0);
+            }
+        }
+
+        let grid = Generator::<16, 4>::new(config).complete_grid();
+        assert!(grid.is_valid());
+        assert_eq!(clues(&grid), 256);
+    }
+
+    #[test]
+    fn test_minimal_4x4() {
+        let mut generator = Generator::<4, 2>::new(GeneratorConfig::default());
+        let puzzle = generator.generate();
+        assert!(generator.is_unique(puzzle));
+        // Minimal: every remaining clue is needed
+        for (idx, &value) in puzzle.values().iter().enumerate() {
+            if value != 0 {
+                let mut reduced = puzzle;
+                reduced.cells[idx / 4][idx % 4] = 0;
+                assert!(!generator.is_unique(reduced));
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_x_sudoku() {
+        let config = GeneratorConfig {
+            seed: 3,
+            target_clues: Some(30),
+            diagonal: true,
+            ..Default::default()
+        };
+        let mut generator = Generator::<9, 3>::new(config);
+        let grid = generator.complete_grid();
+        for cell in [|i| (i, i), |i| (i, 8 - i)] {
+            let digits = (0..9)
+                .map(cell)
+                .fold(0, |acc, (r, c)| acc | BitMask::<9>::get(grid.cells[r][c]));
+            assert_eq!(digits, BitMask::<9>::all_set());
+        }
+
+        let puzzle = generator.generate();
+        assert!(generator.is_unique(puzzle));
+        let solver = SudokuSolver::<9, 3>::new(puzzle).unwrap();
+        assert!(solver.with_diagonals(true).is_ok());
+    }
+}
@@ -0,0 +1,102 @@
+//! Single-step hints for interactive play.
+
+use std::fmt;
+
+use log::debug;
+
+use super::{SolveOutcome, SudokuSolver};
+use crate::{
+    SudokuError,
+    types::{
+        Board,
+        logic::{Cell, Deduction, LogicBoard},
+        masks::Masks,
+    },
+};
+
+/// The easiest next move on a board
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Hint {
+    /// A logical step: a placement or a candidate elimination, with the
+    /// technique and the cells that justify it
+    Logic(Deduction),
+    /// No implemented technique applies: the digit of the most constrained
+    /// empty cell, taken from the unique solution
+    Reveal { cell: Cell, digit: u8 },
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Hint::Logic(deduction) => write!(f, "{}", deduction),
+            Hint::Reveal {
+                cell: (r, c),
+                digit,
+            } => write!(f, "Reveal: r{}c{} = {}", r + 1, c + 1, digit),
+        }
+    }
+}
+
+/// Easiest next move on `board` under the variant settings of `rules`
+/// (`Masks::default()` for classic Sudoku).
+///
+/// Candidates are derived from the placed digits only, so eliminations refer
+/// to those candidates. Returns `None` when the board is full or has no
+/// unique solution to reveal from.
+pub fn next_hint<const N: usize, const K: usize>(
+    board: &Board<N>,
+    rules: &Masks<N>,
+) -> Result<Option<Hint>, SudokuError> {
+    let logic = LogicBoard::new_with_rules(*board, rules)?;
+    if logic.is_solved() {
+        return Ok(None);
+    }
+    if let Some(deduction) = logic.next_deduction() {
+        debug!("Hint: {}", deduction);
+        return Ok(Some(Hint::Logic(deduction)));
+    }
+
+    let SolveOutcome::Unique(solution) = SudokuSolver::<N, K>::new_with_rules(*board, rules)?
+        .solve()
+        .outcome
+    else {
+        debug!("Logic is stuck and there is no unique solution to reveal from");
+        return Ok(None);
+    };
+    let cell = (0..N)
+        .flat_map(|r| (0..N).map(move |c| (r, c)))
+        .filter(|&(r, c)| board.cells[r][c] == 0)
+        .min_by_key(|&cell| logic.candidates(cell).count_ones());
+    Ok(cell.map(|(r, c)| Hint::Reveal {
+        cell: (r, c),
+        digit: solution.cells[r][c],
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::logic::{Action, Technique};
+
+    #[test]
+    fn test_next_hint() {
+        let easy = Board::<9>::parse(include_str!("../../dataset/input.txt")).unwrap();
+        let Some(Hint::Logic(deduction)) = next_hint::<9, 3>(&easy, &Masks::default()).unwrap()
+        else {
+            panic!("expected a logical hint");
+        };
+        assert_eq!(deduction.technique, Technique::NakedSingle);
+        let Action::Place { cell, digit } = deduction.action else {
+            panic!("expected a placement");
+        };
+        let solution = SudokuSolver::<9, 3>::new(easy).unwrap().solve();
+        assert_eq!(solution.outcome.solutions()[0].cells[cell.0][cell.1], digit);
+
+        // Nothing to deduce and many solutions: no hint
+        let empty = Board::<4>::new([[0; 4]; 4]).unwrap();
+        assert_eq!(next_hint::<4, 2>(&empty, &Masks::default()).unwrap(), None);
+
+        let full = Board::new([[1, 2, 3, 4], [3, 4, 1, 2], [2, 1, 4, 3], [4, 3, 2, 1]]).unwrap();
+        assert_eq!(next_hint::<4, 2>(&full, &Masks::default()).unwrap(), None);
+    }
+}
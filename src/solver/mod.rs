@@ -1,30 +1,121 @@
 // pub mod graph;
+pub mod batch;
+pub mod dynamic;
+pub mod generator;
+mod hint;
+mod outcome;
 pub mod permutations;
-use crate::types::{
-    Board,
-    graph::{Graph, PermutationNode},
-    masks::Masks,
+mod progress;
+mod propagate;
+mod rating;
+pub use hint::{Hint, next_hint};
+pub use outcome::{SolveOutcome, SolveReport, SolveStats, SolveStatus, UnsolvableReason};
+pub use progress::{Progress, check_progress};
+pub use rating::{Difficulty, Rating};
+
+use crate::{
+    SudokuError,
+    types::{
+        Board, CandidateGrid, Regions,
+        graph::{Graph, PermutationNode, PruneStrategy},
+        masks::Masks,
+    },
 };
 use log::{debug, info};
 
 pub struct SudokuSolver<const N: usize, const K: usize> {
     pub board: Board<N>,
+    pub masks: Masks<N>,
+    /// Cells filled by the propagation pre-phase
+    propagated: usize,
+    prune_strategy: PruneStrategy,
 }
 
 impl<const N: usize, const K: usize> SudokuSolver<N, K> {
-    pub fn new(board: Board<N>) -> Self {
-        SudokuSolver { board }
+    /// Phase 1: validate the board and pre-calculate its masks.
+    pub fn new(board: Board<N>) -> Result<Self, SudokuError> {
+        Self::new_with_regions(board, Regions::default())
     }
 
-    pub fn solve(&self) {
+    /// Phase 1 for a jigsaw board: every minigrid is one of `regions`
+    /// instead of a KxK box.
+    pub fn new_with_regions(board: Board<N>, regions: Regions<N>) -> Result<Self, SudokuError> {
+        if K * K != N {
+            return Err(SudokuError::InvalidSize { size: N });
+        }
+
         info!("=== PHASE 1: PARSING AND MASK INITIALIZATION ===");
-        let mut masks = Masks::<N>::default();
-        masks.generate(&self.board);
+        let mut masks = Masks::<N> {
+            regions,
+            ..Default::default()
+        };
+        masks.generate(&board)?;
         info!("✓ Initial allowed masks pre-calculated (optimized)");
 
+        Ok(SudokuSolver {
+            board,
+            masks,
+            propagated: 0,
+            prune_strategy: PruneStrategy::default(),
+        })
+    }
+
+    /// Phase 1 under the variant settings of `rules` (jigsaw regions,
+    /// X-Sudoku diagonals); the digits recorded in `rules` are ignored
+    pub fn new_with_rules(board: Board<N>, rules: &Masks<N>) -> Result<Self, SudokuError> {
+        Self::new_with_regions(board, rules.regions.clone())?.with_diagonals(rules.diagonal)
+    }
+
+    /// Phase 1 from user-supplied pencil marks: cells with a single candidate
+    /// are taken as filled and the minigrid DFS only tries the candidates left
+    /// in the others.
+    pub fn from_candidates(candidates: &CandidateGrid<N>) -> Result<Self, SudokuError> {
+        let mut solver = Self::new(candidates.to_board())?;
+        solver.masks.restrict(candidates);
+        Ok(solver)
+    }
+
+    /// Also require both main diagonals to hold every digit once (X-Sudoku).
+    ///
+    /// A no-op when `enabled` is false. Fails when the givens repeat a digit
+    /// on a diagonal.
+    pub fn with_diagonals(mut self, enabled: bool) -> Result<Self, SudokuError> {
+        if enabled && !self.masks.diagonal {
+            let mut masks = self.masks.cleared();
+            masks.diagonal = true;
+            self.regenerate_masks(masks)?;
+        }
+        Ok(self)
+    }
+
+    /// Fill `masks` (empty, with the variant settings to use) from the current
+    /// board, keeping the per-cell eliminations the givens do not imply (e.g.
+    /// from pencil marks)
+    fn regenerate_masks(&mut self, mut masks: Masks<N>) -> Result<(), SudokuError> {
+        masks.generate(&self.board)?;
+        for (row, previous) in masks.conflict.iter_mut().zip(&self.masks.conflict) {
+            for (conflict, &previous) in row.iter_mut().zip(previous) {
+                *conflict |= previous;
+            }
+        }
+        self.masks = masks;
+        Ok(())
+    }
+
+    /// Pick how phase 4 prunes the graph (degree sweeps by default)
+    pub fn with_prune_strategy(mut self, strategy: PruneStrategy) -> Self {
+        self.prune_strategy = strategy;
+        self
+    }
+
+    /// Run phases 2-3: minigrid permutations and compatibility edges.
+    pub fn build_graph(&self) -> Graph<K, N> {
         info!("=== PHASE 2: MINIGRID PERMUTATION GENERATION ===");
-        let permutations: [Vec<PermutationNode<N, K>>; N] = self.generate_all_permutations(&masks);
+        self.connect(self.generate_all_permutations(&self.masks))
+    }
 
+    /// Log the phase 2 results and run phase 3 on them
+    fn connect(&self, permutations: [Vec<PermutationNode<N, K>>; N]) -> Graph<K, N> {
         // Print permutation counts and details
         for (idx, perms) in permutations.iter().enumerate() {
             info!("Minigrid {}: {} permutation(s)", idx, perms.len());
@@ -33,9 +124,191 @@ impl<const N: usize, const K: usize> SudokuSolver<N, K> {
             }
         }
 
-        let mut graph = Graph::new(permutations);
+        info!("=== PHASE 3: COMPATIBILITY GRAPH CONSTRUCTION ===");
+        let mut graph = self.graph(permutations);
         graph.create_edges();
-        graph.export_to_json("./dataset/graph_data.json");
-        // TODO Prune edges recursively
+        info!(
+            "✓ Graph built: {} vertices, {} edges",
+            graph.vertex_count(),
+            graph.edge_count()
+        );
+        graph
+    }
+
+    /// Graph over `permutations` with this puzzle's regions and diagonals
+    fn graph(&self, permutations: [Vec<PermutationNode<N, K>>; N]) -> Graph<K, N> {
+        Graph::new(permutations)
+            .with_regions(self.masks.regions.clone())
+            .with_diagonals(self.masks.diagonal)
+    }
+
+    fn unsolvable(reason: UnsolvableReason, stats: SolveStats) -> SolveReport<N> {
+        info!("✗ Unsolvable: {}", reason);
+        SolveReport {
+            outcome: SolveOutcome::Unsolvable(reason),
+            stats,
+        }
+    }
+
+    /// Count solutions on the pruned graph without building any board.
+    ///
+    /// See [`Graph::count_solutions`] for the meaning of `limit`.
+    pub fn count_solutions(&self, limit: Option<u128>) -> u128 {
+        let mut graph = self.build_graph();
+        graph.prune_with(self.prune_strategy);
+        let count = graph.count_solutions(limit);
+        info!("✓ Counted {} solution(s)", count);
+        count
+    }
+
+    /// Whether the puzzle has exactly one solution.
+    ///
+    /// Bails out as soon as a minigrid has no valid filling or pruning empties
+    /// one, and stops the search once a second solution is found.
+    pub fn is_unique(&self) -> bool {
+        let permutations = match self.try_generate_permutations(&self.masks) {
+            Ok(permutations) => permutations,
+            Err(reason) => {
+                info!("✗ Unsolvable: {}", reason);
+                return false;
+            }
+        };
+        let mut graph = self.graph(permutations);
+        graph.create_edges();
+        graph.prune_with(self.prune_strategy);
+        if graph.minigrids().iter().any(Vec::is_empty) {
+            info!("✗ Pruning emptied a minigrid");
+            return false;
+        }
+        graph.count_solutions(Some(2)) == 1
+    }
+
+    /// Stream solutions one at a time from the pruned graph.
+    ///
+    /// Nothing is collected up front, so `take(n)` or an early `break` only
+    /// pays for the solutions actually produced.
+    pub fn solutions(&self) -> impl Iterator<Item = Board<N>> + use<N, K> {
+        let mut graph = self.build_graph();
+        graph.prune_with(self.prune_strategy);
+        graph.into_solutions()
+    }
+
+    /// Run every phase, stopping at the first one that proves the puzzle
+    /// unsolvable.
+    pub fn solve(&self) -> SolveReport<N> {
+        let mut stats = SolveStats {
+            propagated_cells: self.propagated,
+            ..Default::default()
+        };
+
+        info!("=== PHASE 2: MINIGRID PERMUTATION GENERATION ===");
+        let permutations = match self.try_generate_permutations(&self.masks) {
+            Ok(permutations) => permutations,
+            Err(reason) => return Self::unsolvable(reason, stats),
+        };
+        let mut graph = self.connect(permutations);
+        stats.permutation_counts = graph.minigrids().iter().map(Vec::len).collect();
+        stats.vertices = graph.vertex_count();
+        stats.edges = graph.edge_count();
+        if let Some((a, b)) = graph.disconnected_pair() {
+            return Self::unsolvable(UnsolvableReason::NoCompatiblePair { a, b }, stats);
+        }
+
+        info!("=== PHASE 4: GRAPH PRUNING ({:?}) ===", self.prune_strategy);
+        let summary = graph.prune_with(self.prune_strategy);
+        stats.prune_strategy = self.prune_strategy;
+        stats.revisions = summary.revisions;
+        stats.pruning_rounds = summary.rounds;
+        for (idx, round) in stats.pruning_rounds.iter().enumerate() {
+            info!(
+                "Round {}: removed {} node(s), {} edge(s)",
+                idx + 1,
+                round.nodes_removed,
+                round.edges_removed
+            );
+        }
+        stats.remaining_vertices = graph.vertex_count();
+        stats.remaining_edges = graph.edge_count();
+        info!(
+            "✓ Pruning reached fixed point after {} round(s){}: {} vertices, {} edges left",
+            stats.pruning_rounds.len(),
+            stats
+                .revisions
+                .map(|revisions| format!(", {} revision(s)", revisions))
+                .unwrap_or_default(),
+            stats.remaining_vertices,
+            stats.remaining_edges
+        );
+        if let Some(box_idx) = graph.minigrids().iter().position(Vec::is_empty) {
+            return Self::unsolvable(UnsolvableReason::PrunedEmpty { box_idx }, stats);
+        }
+
+        info!("=== PHASE 5: SOLUTION EXTRACTION ===");
+        let solutions = graph.extract_solutions();
+        info!("✓ Found {} solution(s)", solutions.len());
+        for (idx, solution) in solutions.iter().enumerate() {
+            debug!("Solution {}:\n{}", idx + 1, solution);
+        }
+
+        SolveReport {
+            outcome: SolveOutcome::from_solutions(solutions),
+            stats,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::graph::PruneStrategy;
+
+    #[test]
+    fn test_x_sudoku() {
+        // All 4x4 grids whose two diagonals hold 1..4, under every pruning strategy
+        let empty = Board::<4>::new([[0; 4]; 4]).unwrap();
+        for strategy in [
+            PruneStrategy::Degree,
+            PruneStrategy::ArcConsistency,
+            PruneStrategy::BandConsistency,
+        ] {
+            let solver = SudokuSolver::<4, 2>::new(empty)
+                .unwrap()
+                .with_diagonals(true)
+                .unwrap()
+                .with_prune_strategy(strategy);
+            assert_eq!(solver.count_solutions(None), 48);
+            let outcome = solver.solve().outcome;
+            assert_eq!(outcome.solution_count(), 48);
+            for solution in outcome.solutions() {
+                let main: Vec<u8> = (0..4).map(|i| solution.cells[i][i]).collect();
+                let anti: Vec<u8> = (0..4).map(|i| solution.cells[i][3 - i]).collect();
+                assert!((1..=4).all(|digit| main.contains(&digit) && anti.contains(&digit)));
+            }
+        }
+
+        // Unique only thanks to the diagonals
+        let board =
+            Board::<9>::from_line(include_str!("../../benches/fixtures/x_9x9.txt")).unwrap();
+        let solution =
+            Board::<9>::from_line(include_str!("../../benches/fixtures/x_9x9_solution.txt"))
+                .unwrap();
+        let x = SudokuSolver::<9, 3>::new(board)
+            .unwrap()
+            .with_diagonals(true)
+            .unwrap()
+            .with_propagation(true);
+        assert_eq!(x.solve().outcome, SolveOutcome::Unique(solution));
+        let plain = SudokuSolver::<9, 3>::new(board).unwrap().solve();
+        assert_eq!(plain.outcome.solution_count(), 26);
+
+        let mut repeated = board;
+        repeated.cells[8][8] = 2;
+        assert!(SudokuSolver::<9, 3>::new(repeated).is_ok());
+        assert!(
+            SudokuSolver::<9, 3>::new(repeated)
+                .unwrap()
+                .with_diagonals(true)
+                .is_err()
+        );
     }
 }
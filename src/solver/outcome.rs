@@ -0,0 +1,212 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::types::{
+    Board,
+    graph::{PruneRound, PruneStrategy},
+};
+
+/// Why a puzzle has no solution, named after the phase that proved it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum UnsolvableReason {
+    /// An empty cell of the box has every digit excluded (phase 2)
+    DeadCell {
+        box_idx: usize,
+        row: usize,
+        col: usize,
+    },
+    /// Every cell has candidates, but no filling of the box fits them all (phase 2)
+    EmptyMinigrid { box_idx: usize },
+    /// Two boxes sharing a band or stack have no compatible permutation pair (phase 3)
+    NoCompatiblePair { a: usize, b: usize },
+    /// Pruning removed every permutation of the box (phase 4)
+    PrunedEmpty { box_idx: usize },
+    /// The pruned graph holds no consistent selection (phase 5)
+    NoConsistentSelection,
+}
+
+impl fmt::Display for UnsolvableReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnsolvableReason::DeadCell { box_idx, row, col } => {
+                write!(
+                    f,
+                    "cell at row {row}, col {col} (box {box_idx}) has no candidate"
+                )
+            }
+            UnsolvableReason::EmptyMinigrid { box_idx } => {
+                write!(f, "box {box_idx} has no valid filling")
+            }
+            UnsolvableReason::NoCompatiblePair { a, b } => {
+                write!(f, "boxes {a} and {b} have no compatible permutations")
+            }
+            UnsolvableReason::PrunedEmpty { box_idx } => {
+                write!(f, "pruning removed every permutation of box {box_idx}")
+            }
+            UnsolvableReason::NoConsistentSelection => {
+                write!(f, "no consistent selection of permutations")
+            }
+        }
+    }
+}
+
+/// Classification of a `SolveOutcome` without its boards
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SolveStatus {
+    Unsolvable,
+    Unique,
+    Multiple,
+}
+
+impl SolveStatus {
+    /// Classification of a puzzle with `count` solutions
+    pub fn from_count(count: u128) -> Self {
+        match count {
+            0 => SolveStatus::Unsolvable,
+            1 => SolveStatus::Unique,
+            _ => SolveStatus::Multiple,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SolveStatus::Unsolvable => "unsolvable",
+            SolveStatus::Unique => "unique",
+            SolveStatus::Multiple => "multiple",
+        }
+    }
+}
+
+impl fmt::Display for SolveStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Puzzle classification after solution extraction (paper, section 3.5)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveOutcome<const N: usize> {
+    /// S = 0: no valid completion exists
+    Unsolvable(UnsolvableReason),
+    /// S = 1: well-formed puzzle
+    Unique(Board<N>),
+    /// S > 1: ambiguous puzzle, every solution found
+    Multiple(Vec<Board<N>>),
+}
+
+impl<const N: usize> SolveOutcome<N> {
+    pub fn from_solutions(mut solutions: Vec<Board<N>>) -> Self {
+        match solutions.len() {
+            0 => SolveOutcome::Unsolvable(UnsolvableReason::NoConsistentSelection),
+            1 => SolveOutcome::Unique(solutions.remove(0)),
+            _ => SolveOutcome::Multiple(solutions),
+        }
+    }
+
+    /// Classification without the boards
+    pub fn status(&self) -> SolveStatus {
+        match self {
+            SolveOutcome::Unsolvable(_) => SolveStatus::Unsolvable,
+            SolveOutcome::Unique(_) => SolveStatus::Unique,
+            SolveOutcome::Multiple(_) => SolveStatus::Multiple,
+        }
+    }
+
+    /// Why the puzzle is unsolvable, if it is
+    pub fn reason(&self) -> Option<UnsolvableReason> {
+        match self {
+            SolveOutcome::Unsolvable(reason) => Some(*reason),
+            _ => None,
+        }
+    }
+
+    pub fn solution_count(&self) -> usize {
+        match self {
+            SolveOutcome::Unsolvable(_) => 0,
+            SolveOutcome::Unique(_) => 1,
+            SolveOutcome::Multiple(solutions) => solutions.len(),
+        }
+    }
+
+    pub fn solutions(&self) -> &[Board<N>] {
+        match self {
+            SolveOutcome::Unsolvable(_) => &[],
+            SolveOutcome::Unique(board) => std::slice::from_ref(board),
+            SolveOutcome::Multiple(solutions) => solutions,
+        }
+    }
+}
+
+/// Per-phase statistics collected by `SudokuSolver::solve`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SolveStats {
+    /// Cells filled by the propagation pre-phase, 0 when it did not run
+    pub propagated_cells: usize,
+    /// |P_k| for every minigrid k, before pruning (empty when phase 2 failed)
+    pub permutation_counts: Vec<usize>,
+    /// |V| and |E| of the freshly built compatibility graph
+    pub vertices: usize,
+    pub edges: usize,
+    /// Strategy used in phase 4 and the arc revisions it made (none for degree sweeps)
+    pub prune_strategy: PruneStrategy,
+    pub revisions: Option<usize>,
+    /// Nodes and edges removed by each pruning round
+    pub pruning_rounds: Vec<PruneRound>,
+    /// |V'| and |E'| left at the pruning fixed point
+    pub remaining_vertices: usize,
+    pub remaining_edges: usize,
+}
+
+/// Everything `SudokuSolver::solve` found out about a puzzle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveReport<const N: usize> {
+    pub outcome: SolveOutcome<N>,
+    pub stats: SolveStats,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SolveOutcome, UnsolvableReason};
+    use crate::{SudokuSolver, types::Board};
+
+    #[test]
+    fn test_outcome_classification_4x4() {
+        let report = SudokuSolver::<4, 2>::new(Board::new([[0; 4]; 4]).unwrap())
+            .unwrap()
+            .solve();
+        assert_eq!(report.outcome.solution_count(), 288);
+        assert_eq!(report.stats.permutation_counts, vec![24; 4]);
+        assert_eq!(report.stats.remaining_vertices, report.stats.vertices);
+
+        let unsolvable =
+            Board::new([[0, 0, 1, 2], [0, 0, 0, 0], [3, 0, 0, 0], [4, 0, 0, 0]]).unwrap();
+        let report = SudokuSolver::<4, 2>::new(unsolvable).unwrap().solve();
+        assert_eq!(
+            report.outcome,
+            SolveOutcome::Unsolvable(UnsolvableReason::DeadCell {
+                box_idx: 0,
+                row: 0,
+                col: 0
+            })
+        );
+        assert!(report.stats.permutation_counts.is_empty());
+    }
+
+    #[test]
+    fn test_is_unique() {
+        let easy = Board::<9>::parse(include_str!("../../dataset/input.txt")).unwrap();
+        assert!(SudokuSolver::<9, 3>::new(easy).unwrap().is_unique());
+
+        let empty = Board::new([[0; 4]; 4]).unwrap();
+        assert!(!SudokuSolver::<4, 2>::new(empty).unwrap().is_unique());
+
+        let unsolvable =
+            Board::new([[0, 0, 1, 2], [0, 0, 0, 0], [3, 0, 0, 0], [4, 0, 0, 0]]).unwrap();
+        let solver = SudokuSolver::<4, 2>::new(unsolvable).unwrap();
+        assert!(solver.try_generate_permutations(&solver.masks).is_err());
+        assert!(!solver.is_unique());
+    }
+}
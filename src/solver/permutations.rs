@@ -5,19 +5,24 @@ use crate::types::{
 };
 use log::{debug, trace};
 use rayon::prelude::*;
+use std::sync::{
+    OnceLock,
+    atomic::{AtomicBool, Ordering},
+};
+
+use super::UnsolvableReason;
 
 impl<const N: usize, const K: usize> Minigrid<N, K> {
     // Select the empty cell with the fewest candidates (MRV heuristic)
     // Returns Some(index) of the best cell, or None if no empty cells are found
+    // Err((row, col)) when some empty cell has no candidate left
     // MRV: Minimum Remaining Values
     #[inline(always)]
     fn find_best_cell(
         &self,
         used_mask: &DirtyMask<N>,
         masks: &Masks<N>,
-    ) -> Option<(usize, DirtyMask<N>)> {
-        let start_row = (self.id / Self::K) * Self::K;
-        let start_col = (self.id % Self::K) * Self::K;
+    ) -> Result<Option<(usize, DirtyMask<N>)>, (usize, usize)> {
         let mut best_idx = None;
         let mut best_count = 0;
 
@@ -27,8 +32,7 @@ impl<const N: usize, const K: usize> Minigrid<N, K> {
                 unreachable!("Already handeled by empty_mask")
             }
 
-            let global_row = start_row + (idx / Self::K);
-            let global_col = start_col + (idx % Self::K);
+            let (global_row, global_col) = self.positions[idx];
 
             let mut conflict = masks.conflict[global_row][global_col];
             trace!("Conflict Mask: {}", conflict);
@@ -39,7 +43,7 @@ impl<const N: usize, const K: usize> Minigrid<N, K> {
                     "  Cell[{}] impossible at ({},{})",
                     idx, global_row, global_col
                 );
-                return None;
+                return Err((global_row, global_col));
             }
 
             let incompatible_candidate_count = conflict.get().count_ones();
@@ -47,7 +51,7 @@ impl<const N: usize, const K: usize> Minigrid<N, K> {
                 "Try Cell[{}] Pos({},{}) InvalidCandidates:{} Mask:{}",
                 idx, global_row, global_col, incompatible_candidate_count, conflict
             );
-            if incompatible_candidate_count > best_count {
+            if best_idx.is_none() || incompatible_candidate_count > best_count {
                 // Less candidates found, update best choice
                 best_count = incompatible_candidate_count;
                 best_idx = Some((idx, conflict));
@@ -64,7 +68,7 @@ impl<const N: usize, const K: usize> Minigrid<N, K> {
             "find_best_cell(mg={}, UsedMask={}): {:?}",
             self.id, used_mask, best_idx
         );
-        best_idx
+        Ok(best_idx)
     }
 
     fn generate_permutations_dfs(
@@ -72,8 +76,13 @@ impl<const N: usize, const K: usize> Minigrid<N, K> {
         used_mask: &DirtyMask<N>,
         masks: &Masks<N>,
         results: &mut Vec<PermutationNode<N, K>>,
+        abort: &AtomicBool,
     ) {
-        if let Some((current_idx, conflict)) = self.find_best_cell(used_mask, masks) {
+        if abort.load(Ordering::Relaxed) {
+            return;
+        }
+        let best = self.find_best_cell(used_mask, masks);
+        if let Ok(Some((current_idx, conflict))) = best {
             for num in 1..=N {
                 // Check if num can be placed
                 if !conflict.is_dirty(num) {
@@ -84,13 +93,14 @@ impl<const N: usize, const K: usize> Minigrid<N, K> {
                         used_mask.clone().dirty_set(num),
                         masks,
                         results,
+                        abort,
                     );
                     // Backtrack
                     self.cells[current_idx] = 0;
                     self.empty.set(current_idx);
                 }
             }
-        } else if used_mask.is_all_set() {
+        } else if best.is_ok() && used_mask.is_all_set() {
             trace!("✓ Solution found for mg={}", self.id);
             let cells = self.cells;
             results.push(PermutationNode::from_minigrid(cells));
@@ -102,6 +112,32 @@ impl<const N: usize, const K: usize> Minigrid<N, K> {
 
 impl<const N: usize, const K: usize> super::SudokuSolver<N, K> {
     pub fn generate_all_permutations(&self, masks: &Masks<N>) -> [Vec<PermutationNode<N, K>>; N] {
+        self.generate_permutations(masks, &AtomicBool::new(false), None)
+    }
+
+    /// Like `generate_all_permutations`, but every minigrid stops as soon as
+    /// one of them is found to have no valid filling, and that minigrid is
+    /// reported instead.
+    pub fn try_generate_permutations(
+        &self,
+        masks: &Masks<N>,
+    ) -> Result<[Vec<PermutationNode<N, K>>; N], UnsolvableReason> {
+        let abort = AtomicBool::new(false);
+        let reason = OnceLock::new();
+        let permutations = self.generate_permutations(masks, &abort, Some(&reason));
+        match reason.into_inner() {
+            Some(reason) => Err(reason),
+            None => Ok(permutations),
+        }
+    }
+
+    fn generate_permutations(
+        &self,
+        masks: &Masks<N>,
+        abort: &AtomicBool,
+        // Where the first empty minigrid is reported; generation aborts when set
+        empty_reason: Option<&OnceLock<UnsolvableReason>>,
+    ) -> [Vec<PermutationNode<N, K>>; N] {
         debug!(
             "Starting parallel permutation generation for {} minigrid(s)",
             N
@@ -110,7 +146,7 @@ impl<const N: usize, const K: usize> super::SudokuSolver<N, K> {
         (0..N)
             .into_par_iter()
             .map(|id| {
-                let mut mg = Minigrid::new(id, &self.board);
+                let mut mg = Minigrid::from_region(id, &self.board, masks.regions.cells(id));
                 let mut results = Vec::new();
 
                 // Used mask tracks numbers already present in the minigrid
@@ -119,29 +155,29 @@ impl<const N: usize, const K: usize> super::SudokuSolver<N, K> {
                     "Generating permutations for Minigrid {} (initial_mask={})",
                     id, used_mask
                 );
-                mg.generate_permutations_dfs(&used_mask, masks, &mut results);
+                mg.generate_permutations_dfs(&used_mask, masks, &mut results, abort);
                 debug!("Minigrid {} completed: {} solutions", id, results.len());
 
+                // Minigrids cut short by the abort flag are not the cause
+                if let Some(reason) = empty_reason
+                    && results.is_empty()
+                    && !abort.swap(true, Ordering::Relaxed)
+                {
+                    let cause = match mg.find_best_cell(&used_mask, masks) {
+                        Err((row, col)) => UnsolvableReason::DeadCell {
+                            box_idx: id,
+                            row,
+                            col,
+                        },
+                        Ok(_) => UnsolvableReason::EmptyMinigrid { box_idx: id },
+                    };
+                    debug!("Minigrid {} has no valid filling ({}), aborting", id, cause);
+                    let _ = reason.set(cause);
+                }
                 results
             })
             .collect::<Vec<_>>()
             .try_into()
             .unwrap()
-
-        // let id = 5; // Temporarily using single-threaded for easier debugging
-        // let mut mg = Minigrid::new(id, &self.board);
-        // let mut results = Vec::new();
-
-        // // Used mask tracks numbers already present in the minigrid
-        // let used_mask = masks.boxs[id];
-        // debug!(
-        //     "Generating permutations for Minigrid {} (InitialMask={})",
-        //     id, used_mask
-        // );
-        // mg.generate_permutations_dfs(&used_mask, masks, &mut results);
-        // debug!("Minigrid {} completed: {} solutions", id, results.len());
-        // let mut all_permutations = [(); N].map(|_| Vec::new());
-        // all_permutations[id] = results;
-        // all_permutations
     }
 }
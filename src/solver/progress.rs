@@ -0,0 +1,118 @@
+//! Checking a player's partial progress against the puzzle's solution.
+
+use log::debug;
+use serde::Serialize;
+
+use super::{SolveOutcome, SudokuSolver};
+use crate::{
+    SudokuError,
+    types::{Board, logic::Cell, masks::Masks},
+};
+
+/// What is wrong with an in-progress board, as positions for highlighting
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Progress {
+    /// Filled cells whose digit differs from the unique solution, including
+    /// overwritten givens. Empty when the puzzle has no unique solution.
+    pub wrong: Vec<Cell>,
+    /// Cells sharing a digit with another cell of their row, column, box or
+    /// constrained diagonal
+    pub conflicts: Vec<Cell>,
+    /// Whether the entered digits can still be completed to a solution
+    pub solvable: bool,
+    /// Every cell is filled and correct
+    pub complete: bool,
+}
+
+/// Compare `progress` (the puzzle plus the player's entries) with `puzzle`,
+/// under the variant settings of `rules` (`Masks::default()` for classic
+/// Sudoku).
+///
+/// Fails only when `puzzle` itself is malformed or has conflicting givens.
+pub fn check_progress<const N: usize, const K: usize>(
+    puzzle: &Board<N>,
+    progress: &Board<N>,
+    rules: &Masks<N>,
+) -> Result<Progress, SudokuError> {
+    let conflicts = rules.conflicting_cells(progress);
+    let outcome = SudokuSolver::<N, K>::new_with_rules(*puzzle, rules)?
+        .with_propagation(true)
+        .solve()
+        .outcome;
+
+    let cells = (0..N).flat_map(|r| (0..N).map(move |c| (r, c)));
+    let report = match outcome {
+        SolveOutcome::Unique(solution) => {
+            let wrong: Vec<Cell> = cells
+                .filter(|&(r, c)| {
+                    let digit = progress.cells[r][c];
+                    (digit != 0 || puzzle.cells[r][c] != 0) && digit != solution.cells[r][c]
+                })
+                .collect();
+            let filled = progress.values().iter().all(|&digit| digit != 0);
+            Progress {
+                solvable: wrong.is_empty(),
+                complete: wrong.is_empty() && filled,
+                wrong,
+                conflicts,
+            }
+        }
+        SolveOutcome::Multiple(_) => {
+            // No single truth to compare with: ask whether the entries still extend
+            let givens_kept = cells.clone().all(|(r, c)| {
+                puzzle.cells[r][c] == 0 || puzzle.cells[r][c] == progress.cells[r][c]
+            });
+            let solvable = givens_kept
+                && conflicts.is_empty()
+                && SudokuSolver::<N, K>::new_with_rules(*progress, rules)
+                    .map(|solver| solver.with_propagation(true).count_solutions(Some(1)) > 0)
+                    .unwrap_or(false);
+            Progress {
+                solvable,
+                complete: solvable && progress.values().iter().all(|&digit| digit != 0),
+                wrong: Vec::new(),
+                conflicts,
+            }
+        }
+        SolveOutcome::Unsolvable(_) => Progress {
+            conflicts,
+            ..Default::default()
+        },
+    };
+    debug!(
+        "Progress: {} wrong, {} conflicting, solvable={}",
+        report.wrong.len(),
+        report.conflicts.len(),
+        report.solvable
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_progress() {
+        let puzzle = Board::<9>::parse(include_str!("../../dataset/input.txt")).unwrap();
+        assert_eq!(puzzle.cells[0][3], 0);
+        let solution = SudokuSolver::<9, 3>::new(puzzle).unwrap().solve();
+        let solution = solution.outcome.solutions()[0];
+
+        let mut progress = puzzle;
+        progress.cells[0][3] = solution.cells[0][3];
+        let report = check_progress::<9, 3>(&puzzle, &progress, &Masks::default()).unwrap();
+        assert!(report.solvable && report.wrong.is_empty() && report.conflicts.is_empty());
+        assert!(!report.complete);
+
+        // A wrong digit that also repeats the 7 given at r1c1
+        progress.cells[0][5] = 7;
+        let report = check_progress::<9, 3>(&puzzle, &progress, &Masks::default()).unwrap();
+        assert_eq!(report.wrong, vec![(0, 5)]);
+        assert_eq!(report.conflicts, vec![(0, 0), (0, 5)]);
+        assert!(!report.solvable);
+
+        let report = check_progress::<9, 3>(&puzzle, &solution, &Masks::default()).unwrap();
+        assert!(report.complete);
+    }
+}
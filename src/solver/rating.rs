@@ -0,0 +1,166 @@
+//! Difficulty rating from graph metrics and the logical techniques a human
+//! solver would need.
+
+use std::fmt;
+
+use log::info;
+use serde::Serialize;
+
+use super::SudokuSolver;
+use crate::types::logic::{LogicBoard, Technique};
+
+/// Difficulty bucket of a rated puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+    Diabolical,
+}
+
+impl Difficulty {
+    fn from_score(score: f64) -> Self {
+        match score {
+            s if s < 25.0 => Difficulty::Easy,
+            s if s < 45.0 => Difficulty::Medium,
+            s if s < 70.0 => Difficulty::Hard,
+            _ => Difficulty::Diabolical,
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+            Difficulty::Diabolical => "diabolical",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// Score in 0..=100 with the metrics it was derived from
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Rating {
+    pub score: f64,
+    pub difficulty: Difficulty,
+    /// |P_k| for every minigrid k, before pruning
+    pub permutation_counts: Vec<usize>,
+    pub vertices: usize,
+    pub edges: usize,
+    pub pruning_rounds: usize,
+    /// Hardest technique the logic engine used, `None` if it placed nothing
+    pub hardest_technique: Option<Technique>,
+    /// Whether the logic engine alone solved the puzzle
+    pub solved_by_logic: bool,
+}
+
+/// Share of the logic component a technique stands for
+fn technique_weight(technique: Technique) -> f64 {
+    match technique {
+        Technique::NakedSingle => 0.05,
+        Technique::HiddenSingle => 0.1,
+        Technique::PointingPair => 0.25,
+        Technique::BoxLineReduction => 0.3,
+        Technique::NakedPair => 0.35,
+        Technique::HiddenPair => 0.4,
+        Technique::NakedTriple => 0.45,
+        Technique::HiddenTriple => 0.5,
+        Technique::NakedQuad => 0.55,
+        Technique::HiddenQuad => 0.6,
+        Technique::XWing => 0.65,
+        Technique::Swordfish => 0.75,
+        Technique::XYWing => 0.8,
+    }
+}
+
+impl<const N: usize, const K: usize> SudokuSolver<N, K> {
+    /// Rate the puzzle on a 0-100 scale.
+    ///
+    /// The score mixes the hardest logical technique needed (60%, full weight
+    /// when logic alone gets stuck), the mean log-size of the minigrid
+    /// permutation sets relative to an empty box (30%) and the number of
+    /// pruning rounds (10%, capped at 10 rounds).
+    pub fn rate(&self) -> Rating {
+        let mut graph = self.build_graph();
+        let permutation_counts: Vec<usize> = graph.minigrids().iter().map(Vec::len).collect();
+        let vertices = graph.vertex_count();
+        let edges = graph.edge_count();
+        let pruning_rounds = graph.prune_with(self.prune_strategy).rounds.len();
+
+        let logic = LogicBoard::new_with_rules(self.board, &self.masks);
+        let (hardest_technique, solved_by_logic) = match logic {
+            Ok(mut logic) => {
+                let steps = logic.solve();
+                let hardest = steps.iter().map(|step| step.technique).max();
+                (hardest, logic.is_solved())
+            }
+            _ => (None, false),
+        };
+
+        let logic = match hardest_technique {
+            _ if !solved_by_logic => 1.0,
+            Some(technique) => technique_weight(technique),
+            None => 0.0,
+        };
+        // log2(N!) is the size of an empty box's permutation set
+        let empty_box: f64 = (2..=N).map(|digit| (digit as f64).log2()).sum();
+        let space = permutation_counts
+            .iter()
+            .map(|&count| (count.max(1) as f64).log2() / empty_box)
+            .sum::<f64>()
+            / N as f64;
+        let rounds = pruning_rounds.min(10) as f64 / 10.0;
+
+        let score = 100.0 * (0.6 * logic + 0.3 * space + 0.1 * rounds);
+        let rating = Rating {
+            score,
+            difficulty: Difficulty::from_score(score),
+            permutation_counts,
+            vertices,
+            edges,
+            pruning_rounds,
+            hardest_technique,
+            solved_by_logic,
+        };
+        info!(
+            "✓ Rated {:.1} ({}), hardest technique: {:?}",
+            rating.score, rating.difficulty, rating.hardest_technique
+        );
+        rating
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Difficulty;
+    use crate::{SudokuSolver, types::Board};
+
+    #[test]
+    fn test_rating_orders_puzzles() {
+        let easy = Board::<9>::parse(include_str!("../../dataset/input.txt")).unwrap();
+        let easy = SudokuSolver::<9, 3>::new(easy).unwrap().rate();
+        assert!(easy.solved_by_logic);
+        assert_eq!(easy.difficulty, Difficulty::Easy);
+
+        let hard =
+            Board::<9>::from_line(include_str!("../../benches/fixtures/hard_9x9.txt")).unwrap();
+        let hard = SudokuSolver::<9, 3>::new(hard).unwrap().rate();
+        assert!(hard.hardest_technique > easy.hardest_technique);
+        assert!(hard.difficulty > Difficulty::Easy);
+        assert!(hard.score > easy.score);
+    }
+
+    #[test]
+    fn test_rating_x_sudoku() {
+        let board =
+            Board::<9>::from_line(include_str!("../../benches/fixtures/x_9x9.txt")).unwrap();
+        let solver = SudokuSolver::<9, 3>::new(board).unwrap();
+        let x = solver.with_diagonals(true).unwrap().rate();
+        assert!(x.solved_by_logic);
+        assert!(x.score < 60.0);
+    }
+}
@@ -1,7 +1,15 @@
 use log::{debug, trace};
 use std::fmt;
 
-#[derive(Debug, Clone, Copy)]
+use crate::{
+    SudokuError,
+    types::{
+        Format,
+        logic::{Cell, Unit},
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Board<const N: usize> {
     pub cells: [[u8; N]; N],
 }
@@ -9,10 +17,50 @@ pub struct Board<const N: usize> {
 impl<const N: usize> Board<N> {
     pub const K: usize = N.isqrt();
 
-    pub fn new(cells: [[u8; N]; N]) -> Self {
-        assert_eq!(Self::K * Self::K, N, "N must be a perfect square");
+    pub fn new(cells: [[u8; N]; N]) -> Result<Self, SudokuError> {
+        if Self::K * Self::K != N {
+            return Err(SudokuError::InvalidSize { size: N });
+        }
+        for (row, values) in cells.iter().enumerate() {
+            for (col, &digit) in values.iter().enumerate() {
+                if digit as usize > N {
+                    return Err(SudokuError::DigitOutOfRange {
+                        row,
+                        col,
+                        digit: digit as usize,
+                        max: N,
+                    });
+                }
+            }
+        }
         debug!("Creating new Board with size {}x{}", N, N);
-        Self { cells }
+        Ok(Self { cells })
+    }
+
+    /// Build a board from row-major cell values (0 = empty)
+    pub fn from_slice(values: &[u8]) -> Result<Self, SudokuError> {
+        if values.len() != N * N {
+            return Err(SudokuError::CellCount {
+                expected: N * N,
+                found: values.len(),
+            });
+        }
+
+        let mut cells = [[0u8; N]; N];
+        for (idx, &value) in values.iter().enumerate() {
+            cells[idx / N][idx % N] = value;
+        }
+        Self::new(cells)
+    }
+
+    /// Parse whitespace-separated cell values in row-major order (0 = empty)
+    pub fn parse(input: &str) -> Result<Self, SudokuError> {
+        Self::parse_with(input, Format::Grid)
+    }
+
+    /// Row-major cell values (0 = empty)
+    pub fn values(&self) -> Vec<u8> {
+        self.cells.iter().flatten().copied().collect()
     }
 
     #[inline]
@@ -20,55 +68,57 @@ impl<const N: usize> Board<N> {
         (r / Self::K) * Self::K + (c / Self::K)
     }
 
+    /// Diagonals through a cell: 0 = main (r == c), 1 = anti (r + c == N - 1)
+    pub fn diagonals(r: usize, c: usize) -> impl Iterator<Item = usize> {
+        [r == c, r + c == N - 1]
+            .into_iter()
+            .enumerate()
+            .filter_map(|(diagonal, on)| on.then_some(diagonal))
+    }
+
+    /// Whether no row, column or box repeats a digit
     pub fn is_valid(&self) -> bool {
         trace!("Checking board validity");
-        let k = Self::K;
-        // Check rows and columns
-        for i in 0..N {
-            let mut row_seen = vec![false; N + 1];
-            let mut col_seen = vec![false; N + 1];
-            for j in 0..N {
-                let row_val = self.cells[i][j];
-                let col_val = self.cells[j][i];
-                if row_val != 0 {
-                    if row_seen[row_val as usize] {
-                        debug!("Board invalid: duplicate {} in row {}", row_val, i);
-                        return false;
-                    }
-                    row_seen[row_val as usize] = true;
-                }
-                if col_val != 0 {
-                    if col_seen[col_val as usize] {
-                        debug!("Board invalid: duplicate {} in col {}", col_val, i);
-                        return false;
-                    }
-                    col_seen[col_val as usize] = true;
-                }
-            }
+        let conflicts = self.conflicts();
+        if let Some(&(r, c)) = conflicts.first() {
+            debug!(
+                "Board invalid: {} repeated around ({}, {})",
+                self.cells[r][c], r, c
+            );
         }
-        // Check minigrids
-        for box_row in 0..k {
-            for box_col in 0..k {
-                let mut box_seen = vec![false; N + 1];
-                for i in 0..k {
-                    for j in 0..k {
-                        let val = self.cells[box_row * k + i][box_col * k + j];
-                        if val != 0 {
-                            if box_seen[val as usize] {
-                                debug!(
-                                    "Board invalid: duplicate {} in box ({}, {})",
-                                    val, box_row, box_col
-                                );
-                                return false;
-                            }
-                            box_seen[val as usize] = true;
-                        }
-                    }
+        conflicts.is_empty()
+    }
+
+    /// Every filled cell that shares its digit with another cell of the same
+    /// row, column or box, as sorted (row, col) positions
+    pub fn conflicts(&self) -> Vec<Cell> {
+        self.conflicts_in(Unit::all::<N>().map(Unit::cells::<N>))
+    }
+
+    /// Every filled cell that shares its digit with another cell of one of
+    /// `units`, as sorted (row, col) positions
+    pub fn conflicts_in(&self, units: impl IntoIterator<Item = [Cell; N]>) -> Vec<Cell> {
+        let mut conflicting = vec![[false; N]; N];
+        for cells in units {
+            let mut seen: Vec<Option<Cell>> = vec![None; N + 1];
+            for (r, c) in cells {
+                let digit = self.cells[r][c] as usize;
+                if digit == 0 {
+                    continue;
+                }
+                if let Some((pr, pc)) = seen[digit] {
+                    conflicting[pr][pc] = true;
+                    conflicting[r][c] = true;
+                } else {
+                    seen[digit] = Some((r, c));
                 }
             }
         }
-        trace!("Board is valid");
-        true
+
+        (0..N)
+            .flat_map(|r| (0..N).map(move |c| (r, c)))
+            .filter(|&(r, c)| conflicting[r][c])
+            .collect()
     }
 }
 
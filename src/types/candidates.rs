@@ -0,0 +1,221 @@
+//! Pencil marks: the candidate digits of every cell.
+//!
+//! The line format writes N characters per cell, one slot per digit: the
+//! digit's character when it is a candidate, `.` or `0` when it is not, so a
+//! 9x9 grid takes 729 characters. Cells left with a single candidate count as
+//! filled.
+
+use std::fmt;
+
+use crate::{
+    SudokuError,
+    types::{
+        Board,
+        format::{MAX_CHAR_DIGIT, char_to_digit, digit_to_char},
+        logic::{Cell, digits},
+        masks::{DirtyMask, Masks, Word},
+    },
+};
+
+/// Candidate digits per cell, as a player's pencil marks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CandidateGrid<const N: usize> {
+    /// Bit d-1 set when digit d is still a candidate
+    pub cells: [[DirtyMask<N>; N]; N],
+}
+
+impl<const N: usize> CandidateGrid<N> {
+    /// Pencil marks of a plain board: the digit of every filled cell and every
+    /// digit its row, column and box leave open in the empty ones
+    pub fn from_board(board: &Board<N>) -> Result<Self, SudokuError> {
+        let mut masks = Masks::<N>::default();
+        masks.generate(board)?;
+        Ok(Self::from_fn(|(r, c)| match board.cells[r][c] {
+            0 => !*masks.conflict[r][c].get(),
+            digit => 1 << (digit - 1),
+        }))
+    }
+
+    pub(crate) fn from_fn(candidates: impl Fn(Cell) -> Word) -> Self {
+        Self {
+            cells: std::array::from_fn(|r| {
+                std::array::from_fn(|c| DirtyMask::from_bits(candidates((r, c))))
+            }),
+        }
+    }
+
+    /// Candidate bits of a cell (bit d-1 = digit d)
+    #[inline]
+    pub fn candidates(&self, (r, c): Cell) -> Word {
+        *self.cells[r][c].get()
+    }
+
+    /// Digits ruled out in a cell, in the form of `Masks::conflict`
+    #[inline]
+    pub fn eliminated(&self, cell: Cell) -> DirtyMask<N> {
+        DirtyMask::from_bits(!self.candidates(cell))
+    }
+
+    /// Board holding the digit of every cell left with a single candidate
+    pub fn to_board(&self) -> Board<N> {
+        let mut board = Board { cells: [[0; N]; N] };
+        for (r, row) in self.cells.iter().enumerate() {
+            for (c, mask) in row.iter().enumerate() {
+                if mask.get().count_ones() == 1 {
+                    board.cells[r][c] = mask.get().trailing_zeros() as u8 + 1;
+                }
+            }
+        }
+        board
+    }
+
+    /// Read the N³-character line format; whitespace is ignored
+    pub fn parse(input: &str) -> Result<Self, SudokuError> {
+        if N > MAX_CHAR_DIGIT {
+            return Err(SudokuError::InvalidSize { size: N });
+        }
+        let slots: Vec<char> = input.chars().filter(|ch| !ch.is_whitespace()).collect();
+        if slots.len() != N * N * N {
+            return Err(SudokuError::CellCount {
+                expected: N * N * N,
+                found: slots.len(),
+            });
+        }
+
+        let mut grid = Self::from_fn(|_| 0);
+        for (idx, cell) in slots.chunks(N).enumerate() {
+            let (r, c) = (idx / N, idx % N);
+            for (slot, &ch) in cell.iter().enumerate() {
+                match char_to_digit(ch) {
+                    Some(0) => {}
+                    // Each digit may only appear in its own slot
+                    Some(digit) if digit as usize == slot + 1 => {
+                        grid.cells[r][c].dirty_set(slot + 1);
+                    }
+                    _ => {
+                        return Err(SudokuError::Parse {
+                            row: r,
+                            col: c,
+                            token: cell.iter().collect(),
+                        });
+                    }
+                }
+            }
+        }
+        Ok(grid)
+    }
+
+    /// Write the N³-character line format, `.` for missing candidates
+    pub fn to_line(&self) -> Result<String, SudokuError> {
+        if N > MAX_CHAR_DIGIT {
+            return Err(SudokuError::InvalidSize { size: N });
+        }
+        Ok(self
+            .cells
+            .iter()
+            .flatten()
+            .flat_map(|mask| {
+                (1..=N).filter_map(|digit| {
+                    digit_to_char(if mask.is_dirty(digit) { digit as u8 } else { 0 })
+                })
+            })
+            .collect())
+    }
+}
+
+/// Pencil-mark grid: the candidates of each cell side by side, columns padded
+/// to the widest cell and boxes split like the board display. Cells without
+/// any candidate show `-`.
+impl<const N: usize> fmt::Display for CandidateGrid<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let k = N.isqrt();
+        // Past the single-character digits, candidates are comma-separated numbers
+        let separator = if N > MAX_CHAR_DIGIT { "," } else { "" };
+        let marks: Vec<Vec<String>> = self
+            .cells
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|mask| {
+                        let marks: Vec<String> = digits(*mask.get())
+                            .map(|digit| match digit_to_char(digit) {
+                                Some(ch) if N <= MAX_CHAR_DIGIT => ch.to_string(),
+                                _ => digit.to_string(),
+                            })
+                            .collect();
+                        if marks.is_empty() {
+                            "-".to_string()
+                        } else {
+                            marks.join(separator)
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let width = marks.iter().flatten().map(String::len).max().unwrap_or(1);
+
+        for (i, row) in marks.iter().enumerate() {
+            if i > 0 && i % k == 0 {
+                writeln!(f, "{}", "-".repeat(N * (width + 1) + 2 * (k - 1)))?;
+            }
+            for (j, mark) in row.iter().enumerate() {
+                if j > 0 && j % k == 0 {
+                    write!(f, "| ")?;
+                }
+                write!(f, "{:<width$} ", mark)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SudokuSolver, types::logic::LogicBoard};
+
+    #[test]
+    fn test_candidate_grid() {
+        let board = Board::<9>::parse(include_str!("../../dataset/input.txt")).unwrap();
+        let mut grid = CandidateGrid::from_board(&board).unwrap();
+        let line = grid.to_line().unwrap();
+        assert_eq!(line.len(), 729);
+        assert_eq!(&line[..9], "......7..");
+        assert_eq!(CandidateGrid::<9>::parse(&line).unwrap(), grid);
+        assert_eq!(
+            CandidateGrid::<9>::parse(&line.replace('.', "0")).unwrap(),
+            grid
+        );
+        assert_eq!(grid.to_string().lines().count(), 11);
+
+        // A digit outside its slot
+        let mut bad = line.clone();
+        bad.replace_range(9..10, "2");
+        assert_eq!(
+            CandidateGrid::<9>::parse(&bad),
+            Err(SudokuError::Parse {
+                row: 0,
+                col: 1,
+                token: "2..4.....".to_string()
+            })
+        );
+
+        // Pencil marks narrowed to the solution's digit in r1c4 fill that cell
+        let solution = SudokuSolver::<9, 3>::new(board).unwrap().solve();
+        let solution = solution.outcome.solutions()[0];
+        let digit = solution.cells[0][3];
+        grid.cells[0][3] = DirtyMask::from_bits(1 << (digit - 1));
+        assert_eq!(grid.to_board().cells[0][3], digit);
+
+        let solver = SudokuSolver::<9, 3>::from_candidates(&grid).unwrap();
+        assert_eq!(solver.solve().outcome.solutions(), vec![solution]);
+        let mut logic = LogicBoard::from_candidates(&grid).unwrap();
+        logic.solve();
+        assert_eq!(logic.board, solution);
+        assert_eq!(
+            logic.candidate_grid(),
+            CandidateGrid::from_board(&solution).unwrap()
+        );
+    }
+}
@@ -0,0 +1,254 @@
+//! Text formats for reading and writing boards.
+//!
+//! Single-character formats use `.` or `0` for blanks, `1`-`9` for the first
+//! nine digits and letters from `A` (= 10) upwards, so they cover boards up
+//! to 25x25 (35 at most).
+
+use crate::{SudokuError, solver::dynamic::detect_size, types::Board};
+
+/// Supported puzzle layouts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Whitespace-separated integers, 0 for empty cells
+    Grid,
+    /// One character per cell on a single line, e.g. 81 characters for 9x9
+    Line,
+    /// The pipe-and-dash grid printed by `impl Display for Board`
+    Pretty,
+    /// One line of characters per row, `#` lines are comments
+    Sdk,
+}
+
+/// Highest digit with a single-character form
+pub(crate) const MAX_CHAR_DIGIT: usize = 35;
+
+pub fn digit_to_char(digit: u8) -> Option<char> {
+    match digit {
+        0 => Some('.'),
+        _ => char::from_digit(digit as u32, MAX_CHAR_DIGIT as u32 + 1)
+            .map(|ch| ch.to_ascii_uppercase()),
+    }
+}
+
+pub fn char_to_digit(ch: char) -> Option<u8> {
+    match ch {
+        '.' => Some(0),
+        _ => ch
+            .to_digit(MAX_CHAR_DIGIT as u32 + 1)
+            .map(|digit| digit as u8),
+    }
+}
+
+/// Blank lines and `#` comments, skipped wherever puzzles are read line by line
+pub(crate) fn is_comment(line: &str) -> bool {
+    let line = line.trim();
+    line.is_empty() || line.starts_with('#')
+}
+
+/// `|` column separators and `---` box separators of the pretty grid
+fn is_separator(token: &str) -> bool {
+    token.chars().all(|ch| ch == '|' || ch == '-' || ch == '+')
+}
+
+/// Split input into one token per cell
+fn cell_tokens(input: &str, format: Format) -> Vec<&str> {
+    match format {
+        Format::Grid => input.split_whitespace().collect(),
+        Format::Pretty => input
+            .split_whitespace()
+            .filter(|token| !is_separator(token))
+            .collect(),
+        Format::Line | Format::Sdk => input
+            .lines()
+            .filter(|line| !is_comment(line))
+            .flat_map(|line| {
+                line.char_indices()
+                    .filter(|(_, ch)| !ch.is_whitespace())
+                    .map(move |(idx, ch)| &line[idx..idx + ch.len_utf8()])
+            })
+            .collect(),
+    }
+}
+
+fn token_value(token: &str, format: Format) -> Option<u8> {
+    match format {
+        Format::Grid | Format::Pretty if token == "." => Some(0),
+        Format::Grid | Format::Pretty => token.parse().ok(),
+        Format::Line | Format::Sdk => token.chars().next().and_then(char_to_digit),
+    }
+}
+
+/// Read row-major cell values, detecting the board size when not given.
+///
+/// Returns the board size together with the values.
+pub fn read_values(
+    input: &str,
+    format: Format,
+    size: Option<usize>,
+) -> Result<(usize, Vec<u8>), SudokuError> {
+    let tokens = cell_tokens(input, format);
+    let size = match size {
+        Some(size) => size,
+        None => detect_size(tokens.len())?,
+    };
+
+    let values = tokens
+        .into_iter()
+        .enumerate()
+        .map(|(idx, token)| {
+            token_value(token, format).ok_or_else(|| SudokuError::Parse {
+                row: idx / size,
+                col: idx % size,
+                token: token.to_string(),
+            })
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((size, values))
+}
+
+impl<const N: usize> Board<N> {
+    pub fn parse_with(input: &str, format: Format) -> Result<Self, SudokuError> {
+        let (_, values) = read_values(input, format, Some(N))?;
+        Self::from_slice(&values)
+    }
+
+    pub fn to_format(&self, format: Format) -> Result<String, SudokuError> {
+        match format {
+            Format::Grid => Ok(self
+                .cells
+                .iter()
+                .map(|row| {
+                    let row: Vec<String> = row.iter().map(u8::to_string).collect();
+                    row.join(" ") + "\n"
+                })
+                .collect()),
+            Format::Pretty => Ok(self.to_string()),
+            Format::Line => self.char_rows().map(|rows| rows.concat()),
+            Format::Sdk => self
+                .char_rows()
+                .map(|rows| rows.into_iter().map(|row| row + "\n").collect()),
+        }
+    }
+
+    /// Single-line form, one character per cell
+    pub fn from_line(line: &str) -> Result<Self, SudokuError> {
+        Self::parse_with(line, Format::Line)
+    }
+
+    pub fn to_line(&self) -> Result<String, SudokuError> {
+        self.to_format(Format::Line)
+    }
+
+    fn char_rows(&self) -> Result<Vec<String>, SudokuError> {
+        if N > MAX_CHAR_DIGIT {
+            return Err(SudokuError::InvalidSize { size: N });
+        }
+        Ok(self
+            .cells
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .filter_map(|&digit| digit_to_char(digit))
+                    .collect()
+            })
+            .collect())
+    }
+}
+
+/// Parse an SDM collection: one single-line puzzle per line, blank and `#` lines skipped
+pub fn parse_sdm<const N: usize>(input: &str) -> Vec<Result<Board<N>, SudokuError>> {
+    input
+        .lines()
+        .filter(|line| !is_comment(line))
+        .map(Board::from_line)
+        .collect()
+}
+
+pub fn to_sdm<const N: usize>(boards: &[Board<N>]) -> Result<String, SudokuError> {
+    boards
+        .iter()
+        .map(|board| board.to_line().map(|line| line + "\n"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helper::pattern_values;
+
+    const PUZZLE: &str =
+        "745.9.....3215..46...28.5.32......6.98.6..351...54.2.73.8.....2.2.76..1..6.9.8.34";
+
+    fn pattern_board<const N: usize>(holes: usize) -> Board<N> {
+        let mut cells = pattern_values(N);
+        for idx in (0..N * N).step_by(N * N / holes) {
+            cells[idx] = 0;
+        }
+        Board::from_slice(&cells).unwrap()
+    }
+
+    fn assert_round_trips<const N: usize>(board: &Board<N>) {
+        for format in [Format::Grid, Format::Line, Format::Pretty, Format::Sdk] {
+            let text = board.to_format(format).unwrap();
+            assert_eq!(
+                &Board::<N>::parse_with(&text, format).unwrap(),
+                board,
+                "{format:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_round_trips() {
+        let board = Board::<9>::from_line(PUZZLE).unwrap();
+        assert_eq!(board.to_line().unwrap(), PUZZLE);
+        assert_round_trips(&board);
+        assert_round_trips(&pattern_board::<4>(5));
+        assert_round_trips(&pattern_board::<16>(40));
+        assert_round_trips(&pattern_board::<25>(100));
+
+        let line = pattern_board::<16>(40).to_line().unwrap();
+        assert!(line.contains('G'));
+        assert_eq!(
+            Board::<16>::from_line(&line.to_lowercase()).unwrap(),
+            pattern_board::<16>(40)
+        );
+    }
+
+    #[test]
+    fn test_sdm() {
+        let boards = vec![pattern_board::<9>(30), pattern_board::<9>(50)];
+        let sdm = to_sdm(&boards).unwrap();
+        assert_eq!(sdm.lines().count(), 2);
+
+        let commented = format!("# corpus\n\n{sdm}");
+        let parsed: Vec<_> = parse_sdm::<9>(&commented)
+            .into_iter()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(parsed, boards);
+    }
+
+    #[test]
+    fn test_line_errors() {
+        assert_eq!(
+            Board::<9>::from_line(&PUZZLE[..80]),
+            Err(SudokuError::CellCount {
+                expected: 81,
+                found: 80
+            })
+        );
+        let mut bad = PUZZLE.to_string();
+        bad.replace_range(10..11, "*");
+        assert_eq!(
+            Board::<9>::from_line(&bad),
+            Err(SudokuError::Parse {
+                row: 1,
+                col: 1,
+                token: "*".to_string()
+            })
+        );
+        assert_eq!(read_values(PUZZLE, Format::Line, None).unwrap().0, 9);
+        assert!(pattern_board::<36>(10).to_line().is_err());
+    }
+}
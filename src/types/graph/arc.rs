@@ -0,0 +1,119 @@
+//! Phase 4 alternative: AC-3 arc consistency over minigrid permutation domains.
+//!
+//! Every minigrid is a variable whose domain is its permutation list, and every
+//! `Relation::Row` / `Relation::Col` pair is a binary constraint given by the
+//! compatibility edges. Only arcs whose neighbour lost values are revisited.
+
+use std::collections::VecDeque;
+
+use log::{debug, trace};
+
+use super::{Graph, PermutationNode, PruneRound};
+
+/// Whether `node` still has an edge to a live permutation of minigrid `other`.
+///
+/// Relies on `compatible` being sorted by (minigrid id, permutation id).
+fn has_support<const N: usize, const K: usize>(
+    node: &PermutationNode<N, K>,
+    other: usize,
+    alive: &[bool],
+) -> bool {
+    let start = node.compatible.partition_point(|&(mg, _)| mg < other);
+    node.compatible[start..]
+        .iter()
+        .take_while(|&&(mg, _)| mg == other)
+        .any(|&(_, perm)| alive[perm])
+}
+
+impl<const K: usize, const N: usize> Graph<K, N> {
+    /// Drop every permutation that lacks support in some related minigrid,
+    /// using an AC-3 worklist of directed arcs.
+    ///
+    /// Returns the removal (as a single round) and the number of arc revisions.
+    pub fn arc_consistency(&mut self) -> (PruneRound, usize) {
+        let related: [Vec<usize>; N] = std::array::from_fn(|mg| self.related(mg));
+        let mut alive: [Vec<bool>; N] =
+            std::array::from_fn(|mg| vec![true; self.minigrids[mg].len()]);
+
+        let mut queued = vec![false; N * N];
+        let mut worklist = VecDeque::new();
+        for (a, others) in related.iter().enumerate() {
+            for &b in others {
+                queued[a * N + b] = true;
+                worklist.push_back((a, b));
+            }
+        }
+
+        let mut revisions = 0;
+        while let Some((a, b)) = worklist.pop_front() {
+            queued[a * N + b] = false;
+            revisions += 1;
+
+            // Revise arc a -> b
+            let unsupported: Vec<usize> = self.minigrids[a]
+                .iter()
+                .enumerate()
+                .filter(|&(p, node)| alive[a][p] && !has_support(node, b, &alive[b]))
+                .map(|(p, _)| p)
+                .collect();
+            if unsupported.is_empty() {
+                continue;
+            }
+            trace!(
+                "Arc {} -> {}: {} permutation(s) lost support",
+                a,
+                b,
+                unsupported.len()
+            );
+            for p in unsupported {
+                alive[a][p] = false;
+            }
+
+            for &c in &related[a] {
+                if c != b && !queued[c * N + a] {
+                    queued[c * N + a] = true;
+                    worklist.push_back((c, a));
+                }
+            }
+        }
+
+        let round = self.retain_nodes(&alive);
+        debug!(
+            "Arc consistency: {} revision(s), removed {} node(s), {} edge(s)",
+            revisions, round.nodes_removed, round.edges_removed
+        );
+        (round, revisions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        SudokuSolver,
+        types::{Board, graph::PruneStrategy},
+    };
+
+    #[test]
+    fn test_arc_consistency_matches_degree_fixed_point() {
+        let board =
+            Board::<9>::from_line(include_str!("../../../benches/fixtures/hard_9x9.txt")).unwrap();
+        let solver = SudokuSolver::<9, 3>::new(board).unwrap();
+
+        let mut degree = solver.build_graph();
+        let vertices = degree.vertex_count();
+        let degree_stats = degree.prune_with(PruneStrategy::Degree);
+        let mut arc = solver.build_graph();
+        let arc_stats = arc.prune_with(PruneStrategy::ArcConsistency);
+
+        assert_eq!(degree_stats.revisions, None);
+        assert!(arc_stats.revisions.is_some_and(|revisions| revisions > 0));
+        // A single degree sweep keeps nodes whose support only disappears in
+        // that sweep; AC-3 follows the cascade in one pass
+        assert!(degree_stats.rounds.len() > 1);
+        assert_eq!(arc_stats.rounds.len(), 1);
+        assert!(arc.vertex_count() < vertices - degree_stats.rounds[0].nodes_removed);
+        assert_eq!(arc.vertex_count(), degree.vertex_count());
+        assert_eq!(arc.edge_count(), degree.edge_count());
+        assert_eq!(arc.extract_solutions(), degree.extract_solutions());
+    }
+}
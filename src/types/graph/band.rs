@@ -0,0 +1,176 @@
+//! Band/stack consistency: a permutation survives only if it belongs to a
+//! complete K-tuple of mutually compatible permutations across the K boxes of
+//! its band (row masks) and across the K boxes of its stack (column masks).
+//!
+//! Pairwise edges cannot see this: a node may have a partner in each band
+//! neighbour while those partners clash with each other.
+
+use log::{debug, trace};
+
+use super::{Graph, PermutationNode, PruneRound};
+use crate::types::masks::DirtyMask;
+
+/// Row masks for bands, column masks for stacks
+fn line_masks<const N: usize, const K: usize>(
+    node: &PermutationNode<N, K>,
+    by_rows: bool,
+) -> &[DirtyMask<N>; K] {
+    if by_rows {
+        &node.row_masks
+    } else {
+        &node.col_masks
+    }
+}
+
+impl<const K: usize, const N: usize> Graph<K, N> {
+    /// Minigrid ids of band `idx` (by rows) or stack `idx` (by columns)
+    fn line_boxes(idx: usize, by_rows: bool) -> [usize; K] {
+        std::array::from_fn(|i| if by_rows { idx * K + i } else { i * K + idx })
+    }
+
+    /// Extend `picked` (one permutation per box of `order`, in order) to a
+    /// complete tuple. Every candidate must be adjacent to the first pick and
+    /// disjoint from the masks accumulated so far, which makes the K picks
+    /// consistent as a tuple rather than only pair by pair.
+    fn complete_tuple(
+        &self,
+        order: &[usize; K],
+        by_rows: bool,
+        acc: &mut [DirtyMask<N>; K],
+        picked: &mut Vec<usize>,
+    ) -> bool {
+        let depth = picked.len();
+        if depth == K {
+            return true;
+        }
+
+        let anchor = &self.minigrids[order[0]][picked[0]];
+        let mg = order[depth];
+        let start = anchor.compatible.partition_point(|&(other, _)| other < mg);
+        for &(_, perm) in anchor.compatible[start..]
+            .iter()
+            .take_while(|&&(other, _)| other == mg)
+        {
+            let masks = line_masks(&self.minigrids[mg][perm], by_rows);
+            if (0..K).any(|line| acc[line].is_conflicting(&masks[line])) {
+                continue;
+            }
+
+            let saved = *acc;
+            for line in 0..K {
+                acc[line] |= masks[line];
+            }
+            picked.push(perm);
+            if self.complete_tuple(order, by_rows, acc, picked) {
+                return true;
+            }
+            picked.pop();
+            *acc = saved;
+        }
+        false
+    }
+
+    /// Flag every node of the band/stack that takes part in a complete tuple.
+    ///
+    /// Returns the number of tuple searches made.
+    fn mark_tuples(&self, idx: usize, by_rows: bool, marked: &mut [Vec<bool>; N]) -> usize {
+        let boxes = Self::line_boxes(idx, by_rows);
+        let mut searches = 0;
+
+        for (pos, &mg) in boxes.iter().enumerate() {
+            // Search order: this box first, then the rest of the band in id order
+            let mut order = boxes;
+            order[..=pos].rotate_right(1);
+
+            for perm in 0..self.minigrids[mg].len() {
+                if marked[mg][perm] {
+                    continue;
+                }
+                searches += 1;
+                let mut acc = *line_masks(&self.minigrids[mg][perm], by_rows);
+                let mut picked = vec![perm];
+                if self.complete_tuple(&order, by_rows, &mut acc, &mut picked) {
+                    for (&other, &pick) in order.iter().zip(&picked) {
+                        marked[other][pick] = true;
+                    }
+                } else {
+                    trace!("M-{}-{}: no complete tuple in line {}", mg, perm, idx);
+                }
+            }
+        }
+        searches
+    }
+
+    /// Drop every node missing from all complete band tuples or all complete
+    /// stack tuples.
+    ///
+    /// Returns the removal and the number of tuple searches made.
+    pub fn band_consistency(&mut self) -> (PruneRound, usize) {
+        if !self.regions.is_square() {
+            debug!("Band consistency skipped: jigsaw regions form no bands");
+            return (PruneRound::default(), 0);
+        }
+        let mut in_band: [Vec<bool>; N] =
+            std::array::from_fn(|mg| vec![false; self.minigrids[mg].len()]);
+        let mut in_stack = in_band.clone();
+
+        let mut searches = 0;
+        for idx in 0..K {
+            searches += self.mark_tuples(idx, true, &mut in_band);
+            searches += self.mark_tuples(idx, false, &mut in_stack);
+        }
+
+        let keep: [Vec<bool>; N] = std::array::from_fn(|mg| {
+            in_band[mg]
+                .iter()
+                .zip(&in_stack[mg])
+                .map(|(&band, &stack)| band && stack)
+                .collect()
+        });
+        let round = self.retain_nodes(&keep);
+        debug!(
+            "Band consistency: {} tuple search(es), removed {} node(s), {} edge(s)",
+            searches, round.nodes_removed, round.edges_removed
+        );
+        (round, searches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        SudokuSolver,
+        types::{
+            Board,
+            graph::{Graph, PruneStrategy},
+        },
+    };
+
+    /// Arc consistency keeps three permutations here that have a partner in
+    /// every neighbouring box but no complete band or stack tuple
+    const PAIRWISE_ONLY: &str =
+        "7.5....28.3..5........8.......83.46.9846....1.135.9.8...84....2....6.8.55...287.4";
+
+    fn prune_both<const N: usize, const K: usize>(board: Board<N>) -> (Graph<K, N>, Graph<K, N>) {
+        let solver = SudokuSolver::<N, K>::new(board).unwrap();
+        let mut arc = solver.build_graph();
+        arc.prune_with(PruneStrategy::ArcConsistency);
+        let mut band = solver.build_graph();
+        band.prune_with(PruneStrategy::BandConsistency);
+        assert_eq!(band.extract_solutions(), arc.extract_solutions());
+        (arc, band)
+    }
+
+    #[test]
+    fn test_band_consistency_tightens_arc_consistency() {
+        let (arc, band) = prune_both::<9, 3>(Board::from_line(PAIRWISE_ONLY).unwrap());
+        assert_eq!(arc.vertex_count(), 37);
+        assert_eq!(band.vertex_count(), 34);
+        assert!(band.edge_count() < arc.edge_count());
+
+        let (_, band) = prune_both::<16, 4>(
+            Board::from_line(include_str!("../../../benches/fixtures/unique_16x16.txt")).unwrap(),
+        );
+        assert_eq!(band.extract_solutions().len(), 1);
+    }
+}
@@ -22,4 +22,10 @@ impl<const N: usize, const K: usize> PermutationNode<N, K> {
         }
         true
     }
+
+    /// Diagonal-compatibility for two minigrids on the same X-Sudoku diagonal
+    /// (0 = main, 1 = anti): their cells on it must not share a digit.
+    pub fn check_diagonal_compatible(&self, other: &Self, diagonal: usize) -> bool {
+        !self.diag_masks[diagonal].is_conflicting(&other.diag_masks[diagonal])
+    }
 }
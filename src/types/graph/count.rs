@@ -0,0 +1,210 @@
+//! Solution counting without building boards.
+//!
+//! Minigrids are filled in id order, band by band. Whether the remaining
+//! minigrids can be completed depends only on the digits already used in every
+//! stack column and in the rows of the current band (plus both diagonals for
+//! X-Sudoku), so sub-counts are memoized on exactly that state.
+//!
+//! Jigsaw regions have no bands: they are filled in id order and memoized on
+//! the digits used in the rows, columns and diagonals later regions touch.
+
+use std::collections::HashMap;
+
+use log::debug;
+
+use super::Graph;
+use crate::types::masks::Word;
+
+/// Column masks of every stack column, the row masks of the current band and
+/// the two diagonal masks (for jigsaws: every row, column and diagonal mask
+/// a later region touches)
+type CountKey = (usize, Vec<Word>);
+
+impl<const K: usize, const N: usize> Graph<K, N> {
+    /// Number of consistent selections of one permutation per minigrid, i.e.
+    /// the number of solutions.
+    ///
+    /// With `limit`, counting stops once `limit` solutions are known and
+    /// returns `limit`, so `Some(2)` is enough to tell unique from ambiguous.
+    /// Without it the count saturates at `u128::MAX`.
+    pub fn count_solutions(&self, limit: Option<u128>) -> u128 {
+        if self.minigrids.iter().any(Vec::is_empty) {
+            return 0;
+        }
+        let cap = limit.unwrap_or(u128::MAX);
+        if !self.regions.is_square() {
+            return self.count_regions(cap);
+        }
+        let mut memo = HashMap::new();
+        let mut state = vec![0; N + K + 2];
+        let count = self.count_from(0, &mut state, cap, &mut memo);
+        debug!(
+            "Counted {} solution(s) with {} memoized state(s)",
+            count,
+            memo.len()
+        );
+        count
+    }
+
+    /// Completions of minigrids `mg..N` given `state`, capped at `cap`
+    fn count_from(
+        &self,
+        mg: usize,
+        state: &mut Vec<Word>,
+        cap: u128,
+        memo: &mut HashMap<CountKey, u128>,
+    ) -> u128 {
+        if mg == N {
+            return 1;
+        }
+        // A new band starts with empty rows
+        if mg.is_multiple_of(K) {
+            state[N..N + K].fill(0);
+        }
+        let key = (mg, state.clone());
+        if let Some(&count) = memo.get(&key) {
+            return count;
+        }
+
+        let stack = (mg % K) * K;
+        let diagonals: Vec<usize> = self.box_diagonals(mg).collect();
+        let mut total: u128 = 0;
+        for node in &self.minigrids[mg] {
+            let fits = (0..K).all(|i| {
+                state[stack + i] & node.col_masks[i].get() == 0
+                    && state[N + i] & node.row_masks[i].get() == 0
+            }) && diagonals
+                .iter()
+                .all(|&d| state[N + K + d] & node.diag_masks[d].get() == 0);
+            if !fits {
+                continue;
+            }
+
+            let saved = state.clone();
+            for i in 0..K {
+                state[stack + i] |= node.col_masks[i].get();
+                state[N + i] |= node.row_masks[i].get();
+            }
+            for &d in &diagonals {
+                state[N + K + d] |= node.diag_masks[d].get();
+            }
+            total = total
+                .saturating_add(self.count_from(mg + 1, state, cap, memo))
+                .min(cap);
+            *state = saved;
+            if total == cap {
+                break;
+            }
+        }
+
+        memo.insert(key, total);
+        total
+    }
+
+    /// `count_solutions` over jigsaw regions, capped at `cap`
+    fn count_regions(&self, cap: u128) -> u128 {
+        let units: Vec<Vec<Vec<usize>>> = (0..N).map(|mg| self.cell_units(mg)).collect();
+        let masks = self.unit_masks(&units);
+        // live[mg][unit]: whether a region from mg on lies on the unit
+        let mut live = vec![vec![false; 2 * N + 2]; N + 1];
+        for mg in (0..N).rev() {
+            live[mg] = live[mg + 1].clone();
+            for &unit in units[mg].iter().flatten() {
+                live[mg][unit] = true;
+            }
+        }
+
+        let mut memo = HashMap::new();
+        let mut state = vec![0; 2 * N + 2];
+        let count = Self::count_regions_from(0, &masks, &live, &mut state, cap, &mut memo);
+        debug!(
+            "Counted {} solution(s) over jigsaw regions with {} memoized state(s)",
+            count,
+            memo.len()
+        );
+        count
+    }
+
+    /// Completions of regions `mg..N` given the digits used per unit in
+    /// `state`, capped at `cap`
+    fn count_regions_from(
+        mg: usize,
+        masks: &[Vec<Vec<Word>>],
+        live: &[Vec<bool>],
+        state: &mut Vec<Word>,
+        cap: u128,
+        memo: &mut HashMap<CountKey, u128>,
+    ) -> u128 {
+        if mg == N {
+            return 1;
+        }
+        // Units no later region touches cannot affect the completions
+        let key = state
+            .iter()
+            .zip(&live[mg])
+            .map(|(&used, &live)| if live { used } else { 0 })
+            .collect();
+        let key = (mg, key);
+        if let Some(&count) = memo.get(&key) {
+            return count;
+        }
+
+        let mut total: u128 = 0;
+        for node in &masks[mg] {
+            if node
+                .iter()
+                .zip(state.iter())
+                .any(|(digits, used)| digits & used != 0)
+            {
+                continue;
+            }
+            for (used, digits) in state.iter_mut().zip(node) {
+                *used |= digits;
+            }
+            total = total
+                .saturating_add(Self::count_regions_from(
+                    mg + 1,
+                    masks,
+                    live,
+                    state,
+                    cap,
+                    memo,
+                ))
+                .min(cap);
+            for (used, digits) in state.iter_mut().zip(node) {
+                *used &= !digits;
+            }
+            if total == cap {
+                break;
+            }
+        }
+
+        memo.insert(key, total);
+        total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{SudokuSolver, types::Board};
+
+    #[test]
+    fn test_count_matches_extraction() {
+        let empty = SudokuSolver::<4, 2>::new(Board::new([[0; 4]; 4]).unwrap()).unwrap();
+        let graph = empty.build_graph();
+        assert_eq!(graph.count_solutions(None), 288);
+        assert_eq!(graph.count_solutions(Some(2)), 2);
+
+        // Easy puzzle with its first row cleared: a handful of solutions
+        let mut board = Board::<9>::parse(include_str!("../../../dataset/input.txt")).unwrap();
+        board.cells[0] = [0; 9];
+        board.cells[4][..4].fill(0);
+        let solver = SudokuSolver::<9, 3>::new(board).unwrap();
+        let mut graph = solver.build_graph();
+        graph.prune();
+        let extracted = graph.extract_solutions().len() as u128;
+        assert!(extracted > 1);
+        assert_eq!(graph.count_solutions(None), extracted);
+        assert_eq!(graph.count_solutions(Some(1)), 1);
+    }
+}
@@ -0,0 +1,88 @@
+//! Solution extraction: pick one permutation per minigrid such that every
+//! related pair of picks is joined by a compatibility edge.
+
+use log::debug;
+
+use super::Graph;
+use crate::types::Board;
+
+impl<const K: usize, const N: usize> Graph<K, N> {
+    /// Enumerate every complete board represented by the graph.
+    ///
+    /// Meant to run after [`Graph::prune`], when the remaining search space is small.
+    pub fn extract_solutions(&self) -> Vec<Board<N>> {
+        if self.minigrids.iter().any(Vec::is_empty) {
+            debug!("A minigrid has no permutation left, nothing to extract");
+            return Vec::new();
+        }
+        let solutions: Vec<_> = self.solutions().collect();
+        debug!("Extracted {} solution(s)", solutions.len());
+        solutions
+    }
+
+    /// Write the selected permutation of every minigrid into a board.
+    pub(crate) fn board_from_selection(&self, selected: &[usize; N]) -> Board<N> {
+        let mut board = Board {
+            cells: [[0u8; N]; N],
+        };
+        for (mg, &perm) in selected.iter().enumerate() {
+            let cells = self.minigrids[mg][perm].cells();
+            for (&(r, c), &value) in self.regions.cells(mg).iter().zip(cells) {
+                board.cells[r][c] = value;
+            }
+        }
+        board
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        SudokuSolver,
+        types::{Board, graph::Graph},
+    };
+
+    fn extract<const N: usize, const K: usize>(board: Board<N>) -> Vec<Board<N>> {
+        let solver = SudokuSolver::<N, K>::new(board).unwrap();
+        let mut graph = Graph::<K, N>::new(solver.generate_all_permutations(&solver.masks));
+        graph.create_edges();
+        graph.prune();
+        graph.extract_solutions()
+    }
+
+    #[test]
+    fn test_extract_unique_9x9() {
+        let board = Board::<9>::new([
+            [7, 4, 5, 0, 9, 0, 0, 0, 0],
+            [0, 3, 2, 1, 5, 0, 0, 4, 6],
+            [0, 0, 0, 2, 8, 0, 5, 0, 3],
+            [2, 0, 0, 0, 0, 0, 0, 6, 0],
+            [9, 8, 0, 6, 0, 0, 3, 5, 1],
+            [0, 0, 0, 5, 4, 0, 2, 0, 7],
+            [3, 0, 8, 0, 0, 0, 0, 0, 2],
+            [0, 2, 0, 7, 6, 0, 0, 1, 0],
+            [0, 6, 0, 9, 0, 8, 0, 3, 4],
+        ])
+        .unwrap();
+        let solutions = extract::<9, 3>(board);
+
+        assert_eq!(solutions.len(), 1);
+        let solution = &solutions[0];
+        assert!(solution.is_valid());
+        for r in 0..9 {
+            for c in 0..9 {
+                assert_ne!(solution.cells[r][c], 0);
+                if board.cells[r][c] != 0 {
+                    assert_eq!(solution.cells[r][c], board.cells[r][c]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_extract_all_4x4_grids() {
+        // There are exactly 288 valid 4x4 Sudoku grids
+        let solutions = extract::<4, 2>(Board::new([[0; 4]; 4]).unwrap());
+        assert_eq!(solutions.len(), 288);
+    }
+}
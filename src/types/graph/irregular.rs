@@ -0,0 +1,91 @@
+//! Phase 3 for jigsaw regions: two regions are compatible when their cells on
+//! every shared row, column (and X-Sudoku diagonal) hold different digits.
+//!
+//! Units are numbered row r, column N + c, diagonal 2N + d.
+
+use log::debug;
+
+use super::Graph;
+use crate::types::{Board, masks::Word};
+
+impl<const K: usize, const N: usize> Graph<K, N> {
+    /// Units every cell of minigrid `mg` lies on, in cell order
+    pub(super) fn cell_units(&self, mg: usize) -> Vec<Vec<usize>> {
+        self.regions
+            .cells(mg)
+            .iter()
+            .map(|&(r, c)| {
+                let mut units = vec![r, N + c];
+                if self.diagonal {
+                    units.extend(Board::<N>::diagonals(r, c).map(|d| 2 * N + d));
+                }
+                units
+            })
+            .collect()
+    }
+
+    /// Digits every node places on each unit, per minigrid and node, given
+    /// the `cell_units` of every minigrid
+    pub(super) fn unit_masks(&self, units: &[Vec<Vec<usize>>]) -> Vec<Vec<Vec<Word>>> {
+        (0..N)
+            .map(|mg| {
+                self.minigrids[mg]
+                    .iter()
+                    .map(|node| {
+                        let mut masks = vec![0; 2 * N + 2];
+                        for (cell_units, &digit) in units[mg].iter().zip(node.cells()) {
+                            for &unit in cell_units {
+                                masks[unit] |= 1 << (digit - 1);
+                            }
+                        }
+                        masks
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub(crate) fn create_region_edges(&mut self) {
+        let units: Vec<Vec<Vec<usize>>> = (0..N).map(|mg| self.cell_units(mg)).collect();
+        let touched: Vec<Vec<bool>> = units
+            .iter()
+            .map(|cells| {
+                let mut touched = vec![false; 2 * N + 2];
+                for &unit in cells.iter().flatten() {
+                    touched[unit] = true;
+                }
+                touched
+            })
+            .collect();
+        let masks = self.unit_masks(&units);
+
+        for i in 0..N {
+            for j in (i + 1)..N {
+                let shared: Vec<usize> = (0..2 * N + 2)
+                    .filter(|&unit| touched[i][unit] && touched[j][unit])
+                    .collect();
+                if shared.is_empty() {
+                    continue;
+                }
+
+                let (left, right) = self.minigrids.split_at_mut(j);
+                for (pi_idx, pi) in left[i].iter_mut().enumerate() {
+                    for (pj_idx, pj) in right[0].iter_mut().enumerate() {
+                        let compatible = shared
+                            .iter()
+                            .all(|&unit| masks[i][pi_idx][unit] & masks[j][pj_idx][unit] == 0);
+                        if compatible {
+                            pi.compatible.push((j, pj_idx));
+                            pj.compatible.push((i, pi_idx));
+                        }
+                    }
+                }
+            }
+        }
+        debug!(
+            "Region edges built: {} edge(s) between {} jigsaw region(s)",
+            self.edge_count(),
+            N
+        );
+    }
+}
@@ -1,29 +1,101 @@
+mod arc;
+mod band;
 mod compatibility;
+mod count;
+mod extract;
+mod irregular;
 mod node;
+mod prune;
 mod relationship;
+mod solutions;
 mod visualize;
 
 use log::trace;
+
+use crate::types::Regions;
 pub use node::PermutationNode;
+pub use prune::{PruneRound, PruneStrategy, PruneSummary};
 pub use relationship::Relation;
+pub use solutions::Solutions;
 
 /// Graph structure for storing PermutationNodes and their compatibility edges
 pub struct Graph<const K: usize, const N: usize> {
     /// Array of PermutationNode vectors, one per minigrid
     minigrids: [Vec<PermutationNode<N, K>>; N],
+    /// X-Sudoku: minigrids on the same main diagonal are constrained too
+    diagonal: bool,
+    /// Cells of every minigrid, KxK boxes unless the puzzle is a jigsaw
+    regions: Regions<N>,
 }
 
 impl<const K: usize, const N: usize> Graph<K, N> {
     /// Initialize graph from permutation data and build compatibility edges
     pub fn new(minigrids: [Vec<PermutationNode<N, K>>; N]) -> Self {
-        Self { minigrids }
+        Self {
+            minigrids,
+            diagonal: false,
+            regions: Regions::default(),
+        }
+    }
+
+    /// Use jigsaw regions instead of KxK boxes as the minigrids.
+    ///
+    /// Must be set before [`Graph::create_edges`].
+    pub fn with_regions(mut self, regions: Regions<N>) -> Self {
+        self.regions = regions;
+        self
+    }
+
+    /// Also constrain minigrids sharing a main diagonal (X-Sudoku).
+    ///
+    /// Must be set before [`Graph::create_edges`].
+    pub fn with_diagonals(mut self, enabled: bool) -> Self {
+        self.diagonal = enabled;
+        self
+    }
+
+    /// Permutation nodes of every minigrid
+    pub fn minigrids(&self) -> &[Vec<PermutationNode<N, K>>; N] {
+        &self.minigrids
+    }
+
+    /// Total number of vertices (permutations) in the graph
+    pub fn vertex_count(&self) -> usize {
+        self.minigrids.iter().map(Vec::len).sum()
+    }
+
+    /// Total number of undirected compatibility edges in the graph
+    pub fn edge_count(&self) -> usize {
+        self.minigrids
+            .iter()
+            .flatten()
+            .map(|node| node.compatible.len())
+            .sum::<usize>()
+            / 2
+    }
+
+    /// First pair of related minigrids without a single compatibility edge
+    pub fn disconnected_pair(&self) -> Option<(usize, usize)> {
+        (0..N)
+            .flat_map(|a| (a + 1..N).map(move |b| (a, b)))
+            .filter(|&(a, b)| self.constrains(a, b))
+            .find(|&(a, b)| {
+                !self.minigrids[a].iter().any(|node| {
+                    let start = node.compatible.partition_point(|&(mg, _)| mg < b);
+                    node.compatible.get(start).is_some_and(|&(mg, _)| mg == b)
+                })
+            })
     }
 
     pub fn create_edges(&mut self) {
+        if !self.regions.is_square() {
+            return self.create_region_edges();
+        }
         for i in 0..N {
             for j in (i + 1)..N {
                 // Compute relationship BEFORE borrowing minigrids mutably
                 let relation = self.relationship(i, j);
+                let diagonal = self.shared_diagonal(i, j);
 
                 let (left, right) = self.minigrids.split_at_mut(j);
                 let mgi = &mut left[i];
@@ -31,10 +103,12 @@ impl<const K: usize, const N: usize> Graph<K, N> {
 
                 for (pi_idx, pi) in mgi.iter_mut().enumerate() {
                     for (pj_idx, pj) in mgj.iter_mut().enumerate() {
-                        let compatible = match relation {
-                            Relation::Not => false,
-                            Relation::Row => pi.check_row_compatible(pj),
-                            Relation::Col => pi.check_col_compatible(pj),
+                        let compatible = match (relation, diagonal) {
+                            (Relation::Row, _) => pi.check_row_compatible(pj),
+                            (Relation::Col, _) => pi.check_col_compatible(pj),
+                            // Boxes on one diagonal never share a block-row or block-column
+                            (Relation::Not, Some(d)) => pi.check_diagonal_compatible(pj, d),
+                            (Relation::Not, None) => false,
                         };
 
                         trace!(
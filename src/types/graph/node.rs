@@ -7,6 +7,8 @@ pub struct PermutationNode<const N: usize, const K: usize> {
     cells: [u8; N],
     pub row_masks: [DirtyMask<N>; K],
     pub col_masks: [DirtyMask<N>; K],
+    /// Digits on the minigrid's own main (0) and anti (1) diagonal
+    pub diag_masks: [DirtyMask<N>; 2],
     pub compatible: Vec<(usize, usize)>, // (Minigrid id, Permutation id)
 }
 
@@ -31,9 +33,26 @@ impl<const N: usize, const K: usize> PermutationNode<N, K> {
     pub fn cells(&self) -> &[u8; N] {
         &self.cells
     }
+
+    /// Number of distinct minigrids this node has at least one edge into.
+    ///
+    /// Relies on `compatible` being sorted by minigrid id.
+    pub fn degree(&self) -> usize {
+        let mut degree = 0;
+        let mut last = usize::MAX;
+        for &(mg, _) in &self.compatible {
+            if mg != last {
+                degree += 1;
+                last = mg;
+            }
+        }
+        degree
+    }
+
     pub fn from_minigrid(cells: [u8; N]) -> Self {
         let mut row_masks = [DirtyMask::default(); K];
         let mut col_masks = [DirtyMask::default(); K];
+        let mut diag_masks = [DirtyMask::default(); 2];
 
         for (i, &digit) in cells.iter().enumerate() {
             let r = i / K; // Row
@@ -41,12 +60,19 @@ impl<const N: usize, const K: usize> PermutationNode<N, K> {
             let digit = digit as usize;
             row_masks[r].dirty_set(digit);
             col_masks[c].dirty_set(digit);
+            if r == c {
+                diag_masks[0].dirty_set(digit);
+            }
+            if r + c == K - 1 {
+                diag_masks[1].dirty_set(digit);
+            }
         }
 
         Self {
             cells,
             row_masks,
             col_masks,
+            diag_masks,
             compatible: Vec::new(),
         }
     }
@@ -0,0 +1,197 @@
+//! Phase 4: iterative degree-based pruning of the compatibility graph.
+
+use log::{debug, trace};
+use serde::Serialize;
+
+use super::Graph;
+
+/// Nodes and edges removed by a single pruning round.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PruneRound {
+    pub nodes_removed: usize,
+    pub edges_removed: usize,
+}
+
+/// How phase 4 shrinks the graph before extraction
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PruneStrategy {
+    /// Repeated full sweeps dropping nodes without a neighbour in every related minigrid
+    #[default]
+    Degree,
+    /// AC-3 worklist that only revisits arcs whose neighbour lost permutations
+    ArcConsistency,
+    /// Arc consistency alternated with band/stack tuple checks until neither removes anything
+    BandConsistency,
+}
+
+/// What a pruning strategy removed and how much work it did
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct PruneSummary {
+    /// Rounds that removed something
+    pub rounds: Vec<PruneRound>,
+    /// Directed minigrid arcs checked for support, plus band/stack tuple
+    /// searches; degree sweeps do not revise arcs and leave it empty
+    pub revisions: Option<usize>,
+}
+
+impl<const K: usize, const N: usize> Graph<K, N> {
+    /// Minigrids sharing a block-row, block-column or X-Sudoku diagonal with `mg`.
+    pub fn related(&self, mg: usize) -> Vec<usize> {
+        (0..N).filter(|&other| self.constrains(mg, other)).collect()
+    }
+
+    /// Prune with the given strategy.
+    ///
+    /// Degree sweeps and AC-3 reach the same fixed point; band consistency
+    /// starts from it and can prune further.
+    pub fn prune_with(&mut self, strategy: PruneStrategy) -> PruneSummary {
+        match strategy {
+            PruneStrategy::Degree => PruneSummary {
+                rounds: self.prune(),
+                revisions: None,
+            },
+            PruneStrategy::ArcConsistency => {
+                let (round, revisions) = self.arc_consistency();
+                PruneSummary {
+                    rounds: if round.nodes_removed > 0 {
+                        vec![round]
+                    } else {
+                        Vec::new()
+                    },
+                    revisions: Some(revisions),
+                }
+            }
+            PruneStrategy::BandConsistency => {
+                let mut rounds = Vec::new();
+                let mut total = 0;
+                loop {
+                    let (arc, revisions) = self.arc_consistency();
+                    let (band, searches) = self.band_consistency();
+                    total += revisions + searches;
+                    for round in [arc, band] {
+                        if round.nodes_removed > 0 {
+                            rounds.push(round);
+                        }
+                    }
+                    if band.nodes_removed == 0 {
+                        break PruneSummary {
+                            rounds,
+                            revisions: Some(total),
+                        };
+                    }
+                }
+            }
+        }
+    }
+
+    /// Remove every node that has no compatible partner in at least one of its
+    /// related minigrids, until a fixed point is reached.
+    ///
+    /// Returns one entry per round that removed something.
+    pub fn prune(&mut self) -> Vec<PruneRound> {
+        let required: [usize; N] = std::array::from_fn(|mg| self.related(mg).len());
+        let mut rounds = Vec::new();
+
+        loop {
+            let keep: [Vec<bool>; N] = std::array::from_fn(|mg| {
+                self.minigrids[mg]
+                    .iter()
+                    .map(|node| node.degree() == required[mg])
+                    .collect()
+            });
+
+            let round = self.retain_nodes(&keep);
+            if round.nodes_removed == 0 {
+                break;
+            }
+            debug!(
+                "Pruning round {}: removed {} node(s), {} edge(s)",
+                rounds.len() + 1,
+                round.nodes_removed,
+                round.edges_removed
+            );
+            rounds.push(round);
+        }
+
+        rounds
+    }
+
+    /// Drop every node whose `keep` flag is false, together with its edges,
+    /// and remap the surviving `compatible` indices.
+    ///
+    /// Remapping is monotonic, so adjacency lists stay sorted by
+    /// (minigrid id, permutation id).
+    pub(crate) fn retain_nodes(&mut self, keep: &[Vec<bool>; N]) -> PruneRound {
+        let mut round = PruneRound::default();
+        let edges_before = self.edge_count();
+
+        // Old permutation index -> new index (usize::MAX when removed)
+        let remap: [Vec<usize>; N] = std::array::from_fn(|mg| {
+            let mut next = 0;
+            keep[mg]
+                .iter()
+                .map(|&kept| {
+                    if kept {
+                        next += 1;
+                        next - 1
+                    } else {
+                        usize::MAX
+                    }
+                })
+                .collect()
+        });
+
+        for (mg, nodes) in self.minigrids.iter_mut().enumerate() {
+            let before = nodes.len();
+            let mut idx = 0;
+            nodes.retain(|_| {
+                idx += 1;
+                keep[mg][idx - 1]
+            });
+            round.nodes_removed += before - nodes.len();
+
+            for node in nodes.iter_mut() {
+                node.compatible.retain_mut(|(other_mg, other_perm)| {
+                    let new_idx = remap[*other_mg][*other_perm];
+                    *other_perm = new_idx;
+                    new_idx != usize::MAX
+                });
+            }
+            trace!("Minigrid {}: {} node(s) left", mg, nodes.len());
+        }
+
+        round.edges_removed = edges_before - self.edge_count();
+        round
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{SudokuSolver, types::Board};
+
+    #[test]
+    fn test_prune_easy_9x9_to_single_nodes() {
+        let board = Board::<9>::new([
+            [7, 4, 5, 0, 9, 0, 0, 0, 0],
+            [0, 3, 2, 1, 5, 0, 0, 4, 6],
+            [0, 0, 0, 2, 8, 0, 5, 0, 3],
+            [2, 0, 0, 0, 0, 0, 0, 6, 0],
+            [9, 8, 0, 6, 0, 0, 3, 5, 1],
+            [0, 0, 0, 5, 4, 0, 2, 0, 7],
+            [3, 0, 8, 0, 0, 0, 0, 0, 2],
+            [0, 2, 0, 7, 6, 0, 0, 1, 0],
+            [0, 6, 0, 9, 0, 8, 0, 3, 4],
+        ])
+        .unwrap();
+        let solver = SudokuSolver::<9, 3>::new(board).unwrap();
+        let mut graph = super::Graph::new(solver.generate_all_permutations(&solver.masks));
+        graph.create_edges();
+        let rounds = graph.prune();
+
+        assert!(!rounds.is_empty());
+        for mg in 0..9 {
+            assert_eq!(graph.minigrids[mg].len(), 1, "minigrid {mg}");
+        }
+    }
+}
@@ -82,6 +82,37 @@ impl<const K: usize, const N: usize> super::Graph<K, N> {
         // convert mask to Relation
         Relation::from_mask(mask)
     }
+
+    /// Main diagonals (0 = main, 1 = anti) crossing minigrid `mg`; none unless
+    /// the graph is an X-Sudoku.
+    pub fn box_diagonals(&self, mg: usize) -> impl Iterator<Item = usize> + use<K, N> {
+        let diagonal = self.diagonal;
+        self.regions
+            .diagonals(mg)
+            .into_iter()
+            .enumerate()
+            .filter_map(move |(d, on)| (diagonal && on).then_some(d))
+    }
+
+    /// Diagonal shared by two distinct minigrids, which `relationship` reports
+    /// as `Relation::Not`
+    pub fn shared_diagonal(&self, a: usize, b: usize) -> Option<usize> {
+        if a == b {
+            return None;
+        }
+        self.box_diagonals(a)
+            .find(|&d| self.box_diagonals(b).any(|other| other == d))
+    }
+
+    /// Whether a permutation of `a` restricts the permutations of `b`
+    pub fn constrains(&self, a: usize, b: usize) -> bool {
+        let shares_line = if self.regions.is_square() {
+            self.relationship(a, b) != Relation::Not
+        } else {
+            self.regions.share_line(a, b)
+        };
+        shares_line || self.shared_diagonal(a, b).is_some()
+    }
 }
 
 #[cfg(test)]
@@ -117,5 +148,13 @@ mod tests {
         assert_eq!(g.relationship(0, 4), Relation::Not);
         assert_eq!(g.relationship(2, 6), Relation::Not);
         assert_eq!(g.relationship(5, 5), Relation::Not); // same index -> Not
+
+        // Only X-Sudoku links boxes along a diagonal
+        assert_eq!(g.shared_diagonal(0, 4), None);
+        let g = g.with_diagonals(true);
+        assert_eq!(g.shared_diagonal(0, 8), Some(0));
+        assert_eq!(g.shared_diagonal(2, 4), Some(1));
+        assert_eq!(g.shared_diagonal(0, 2), None);
+        assert!(g.constrains(2, 6) && !g.constrains(1, 5));
     }
 }
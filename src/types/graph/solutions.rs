@@ -0,0 +1,123 @@
+//! Lazy solution enumeration: the extraction DFS with an explicit stack, so
+//! boards are produced one at a time and the walk can stop at any point.
+
+use std::borrow::Borrow;
+
+use log::trace;
+
+use super::Graph;
+use crate::types::Board;
+
+/// Iterator over every complete board of a compatibility graph.
+///
+/// `G` is either a borrowed `&Graph` or an owned `Graph`, so the iterator can
+/// outlive the solver that built the graph.
+pub struct Solutions<G, const K: usize, const N: usize> {
+    graph: G,
+    /// Related minigrids that are selected before each minigrid (lower index)
+    earlier: [Vec<usize>; N],
+    selected: [usize; N],
+    /// Next permutation to try for each minigrid on the current path
+    cursor: [usize; N],
+    depth: usize,
+    done: bool,
+}
+
+impl<G: Borrow<Graph<K, N>>, const K: usize, const N: usize> Solutions<G, K, N> {
+    pub fn new(graph: G) -> Self {
+        let (earlier, done) = {
+            let graph = graph.borrow();
+            let earlier = std::array::from_fn(|mg| {
+                (0..mg)
+                    .filter(|&other| graph.constrains(mg, other))
+                    .collect()
+            });
+            (earlier, graph.minigrids.iter().any(Vec::is_empty))
+        };
+        Self {
+            graph,
+            earlier,
+            selected: [0; N],
+            cursor: [0; N],
+            depth: 0,
+            done,
+        }
+    }
+
+    /// Whether permutation `perm` of `mg` has an edge to every earlier related pick
+    fn fits(&self, mg: usize, perm: usize) -> bool {
+        let node = &self.graph.borrow().minigrids[mg][perm];
+        // Adjacency lists are sorted, so each edge lookup is a binary search
+        self.earlier[mg].iter().all(|&other| {
+            node.compatible
+                .binary_search(&(other, self.selected[other]))
+                .is_ok()
+        })
+    }
+}
+
+impl<G: Borrow<Graph<K, N>>, const K: usize, const N: usize> Iterator for Solutions<G, K, N> {
+    type Item = Board<N>;
+
+    fn next(&mut self) -> Option<Board<N>> {
+        while !self.done {
+            let mg = self.depth;
+            let count = self.graph.borrow().minigrids[mg].len();
+
+            match (self.cursor[mg]..count).find(|&perm| self.fits(mg, perm)) {
+                Some(perm) => {
+                    self.selected[mg] = perm;
+                    self.cursor[mg] = perm + 1;
+                    if mg + 1 < N {
+                        self.depth += 1;
+                        self.cursor[mg + 1] = 0;
+                        continue;
+                    }
+
+                    let graph = self.graph.borrow();
+                    let board = graph.board_from_selection(&self.selected);
+                    if graph.regions.is_valid(&board) {
+                        trace!("✓ Valid selection {:?}", self.selected);
+                        return Some(board);
+                    }
+                    trace!("✗ Selection {:?} failed board validation", self.selected);
+                }
+                // Minigrid exhausted on this path: backtrack
+                None if mg == 0 => self.done = true,
+                None => self.depth -= 1,
+            }
+        }
+        None
+    }
+}
+
+impl<const K: usize, const N: usize> Graph<K, N> {
+    /// Lazily enumerate the boards represented by the graph
+    pub fn solutions(&self) -> Solutions<&Self, K, N> {
+        Solutions::new(self)
+    }
+
+    /// Like [`Graph::solutions`], but the iterator owns the graph
+    pub fn into_solutions(self) -> Solutions<Self, K, N> {
+        Solutions::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::{SudokuSolver, types::Board};
+
+    #[test]
+    fn test_solutions_stream_lazily() {
+        let solver = SudokuSolver::<4, 2>::new(Board::new([[0; 4]; 4]).unwrap()).unwrap();
+
+        let first: Vec<_> = solver.solutions().take(10).collect();
+        assert_eq!(first.len(), 10);
+        assert!(first.iter().all(Board::is_valid));
+
+        let all: HashSet<_> = solver.solutions().map(|board| board.values()).collect();
+        assert_eq!(all.len(), 288);
+    }
+}
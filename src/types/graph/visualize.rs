@@ -1,8 +1,10 @@
+use log::info;
 use serde::{Deserialize, Serialize};
 use std::fs::File;
 use std::io::Write;
 
 use super::Graph;
+use crate::SudokuError;
 
 #[derive(Serialize, Deserialize)]
 pub struct GraphData {
@@ -47,7 +49,7 @@ impl<const K: usize, const N: usize> Graph<K, N> {
     }
 
     /// Export graph to JSON format for visualization
-    pub fn export_to_json(&self, filename: &str) {
+    pub fn export_to_json(&self, filename: &str) -> Result<(), SudokuError> {
         let mut nodes = Vec::new();
         let mut edges = Vec::new();
 
@@ -78,16 +80,19 @@ impl<const K: usize, const N: usize> Graph<K, N> {
             }
         }
 
-        println!("  Nodes: {}", nodes.len());
-        println!("  Edges: {}", edges.len());
+        info!("  Nodes: {}", nodes.len());
+        info!("  Edges: {}", edges.len());
         let graph_data = GraphData { nodes, edges };
         let json = serde_json::to_string_pretty(&graph_data).expect("Json serialization failed");
 
-        let mut file =
-            File::create(filename).unwrap_or_else(|_| panic!("Failed to create {}", filename));
-        file.write_all(json.as_bytes())
-            .unwrap_or_else(|_| panic!("Failed to write in {}", filename));
+        let io_error = |err: std::io::Error| SudokuError::Io {
+            path: filename.to_string(),
+            message: err.to_string(),
+        };
+        let mut file = File::create(filename).map_err(io_error)?;
+        file.write_all(json.as_bytes()).map_err(io_error)?;
 
-        println!("Graph exported to {}", filename);
+        info!("Graph exported to {}", filename);
+        Ok(())
     }
 }
@@ -0,0 +1,76 @@
+use super::{Action, Cell, Deduction, LogicBoard, Technique, find_combination};
+use crate::types::masks::Word;
+
+impl<const N: usize> LogicBoard<N> {
+    /// X-Wing (`size` 2) and Swordfish (`size` 3).
+    ///
+    /// When a digit's spots in `size` rows all fall into the same `size`
+    /// columns, it is removed from those columns in every other row (and the
+    /// same with rows and columns swapped).
+    pub(super) fn fish(&self, size: usize) -> Option<Deduction> {
+        let technique = if size == 2 {
+            Technique::XWing
+        } else {
+            Technique::Swordfish
+        };
+        // `transpose` false: base lines are rows, cover lines are columns
+        let cell = |transpose: bool, base: usize, cover: usize| -> Cell {
+            if transpose {
+                (cover, base)
+            } else {
+                (base, cover)
+            }
+        };
+
+        for digit in 1..=N as u8 {
+            for transpose in [false, true] {
+                let lines: Vec<(usize, Word)> = (0..N)
+                    .filter_map(|base| {
+                        let spots = (0..N)
+                            .filter(|&cover| {
+                                self.has_candidate(cell(transpose, base, cover), digit)
+                            })
+                            .fold(0, |acc: Word, cover| acc | 1 << cover);
+                        (2..=size as u32)
+                            .contains(&spots.count_ones())
+                            .then_some((base, spots))
+                    })
+                    .collect();
+
+                let found = find_combination(lines.len(), size, &mut |picked| {
+                    let covers = picked.iter().fold(0, |acc: Word, &idx| acc | lines[idx].1);
+                    if covers.count_ones() as usize != size {
+                        return None;
+                    }
+                    let bases: Vec<usize> = picked.iter().map(|&idx| lines[idx].0).collect();
+                    let candidates: Vec<_> = (0..N)
+                        .filter(|base| !bases.contains(base))
+                        .flat_map(|base| {
+                            (0..N)
+                                .filter(|cover| covers & (1 << cover) != 0)
+                                .map(move |cover| cell(transpose, base, cover))
+                        })
+                        .filter(|&target| self.has_candidate(target, digit))
+                        .map(|target| (target, digit))
+                        .collect();
+                    (!candidates.is_empty()).then(|| Deduction {
+                        technique,
+                        cells: bases
+                            .iter()
+                            .flat_map(|&base| {
+                                (0..N)
+                                    .map(move |cover| cell(transpose, base, cover))
+                                    .filter(|&pattern| self.has_candidate(pattern, digit))
+                            })
+                            .collect(),
+                        action: Action::Eliminate { candidates },
+                    })
+                });
+                if found.is_some() {
+                    return found;
+                }
+            }
+        }
+        None
+    }
+}
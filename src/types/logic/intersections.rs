@@ -0,0 +1,95 @@
+use super::{Action, Cell, Deduction, LogicBoard, Technique, Unit};
+use crate::types::Board;
+
+impl<const N: usize> LogicBoard<N> {
+    /// Cells of `unit` that still hold `digit` as a candidate
+    pub(super) fn spots(&self, unit: Unit, digit: u8) -> Vec<Cell> {
+        self.unit_cells(unit)
+            .into_iter()
+            .filter(|&cell| self.has_candidate(cell, digit))
+            .collect()
+    }
+
+    /// Eliminations of `digit` from cells of `target` outside `pattern`
+    fn eliminate_outside(
+        &self,
+        technique: Technique,
+        target: Unit,
+        pattern: Vec<Cell>,
+        digit: u8,
+    ) -> Option<Deduction> {
+        let candidates: Vec<_> = self
+            .spots(target, digit)
+            .into_iter()
+            .filter(|cell| !pattern.contains(cell))
+            .map(|cell| (cell, digit))
+            .collect();
+        (!candidates.is_empty()).then_some(Deduction {
+            technique,
+            cells: pattern,
+            action: Action::Eliminate { candidates },
+        })
+    }
+
+    /// A digit confined to one row, column or constrained diagonal inside a
+    /// box is removed from the rest of that line
+    pub(super) fn pointing(&self) -> Option<Deduction> {
+        for b in 0..N {
+            for digit in 1..=N as u8 {
+                let spots = self.spots(Unit::Box(b), digit);
+                let Some(&(r, c)) = spots.first() else {
+                    continue;
+                };
+                if spots.len() < 2 {
+                    continue;
+                }
+                let line = if spots.iter().all(|cell| cell.0 == r) {
+                    Unit::Row(r)
+                } else if spots.iter().all(|cell| cell.1 == c) {
+                    Unit::Col(c)
+                } else if let Some(d) = self.masks.cell_diagonals(r, c).find(|&d| {
+                    spots
+                        .iter()
+                        .all(|&(sr, sc)| Board::<N>::diagonals(sr, sc).any(|other| other == d))
+                }) {
+                    Unit::Diagonal(d)
+                } else {
+                    continue;
+                };
+                let found = self.eliminate_outside(Technique::PointingPair, line, spots, digit);
+                if found.is_some() {
+                    return found;
+                }
+            }
+        }
+        None
+    }
+
+    /// A digit confined to one box inside a row, column or constrained
+    /// diagonal is removed from the rest of that box
+    pub(super) fn box_line_reduction(&self) -> Option<Deduction> {
+        let lines = self.units().filter(|unit| !matches!(unit, Unit::Box(_)));
+        for line in lines {
+            for digit in 1..=N as u8 {
+                let spots = self.spots(line, digit);
+                let Some(&(r, c)) = spots.first() else {
+                    continue;
+                };
+                let b = self.masks.regions.region(r, c);
+                if spots.len() < 2
+                    || spots
+                        .iter()
+                        .any(|&(r, c)| self.masks.regions.region(r, c) != b)
+                {
+                    continue;
+                }
+                let found =
+                    self.eliminate_outside(Technique::BoxLineReduction, Unit::Box(b), spots, digit);
+                if found.is_some() {
+                    return found;
+                }
+            }
+        }
+        None
+    }
+}
@@ -1 +1,438 @@
+//! Human-style logical deduction on top of the `Masks` candidate representation.
+//!
+//! A `LogicBoard` keeps, for every cell, the conflict mask of digits that can no
+//! longer go there (same semantics as `Masks::conflict`). Techniques look for a
+//! pattern and report it as a `Deduction`; applying it places a digit or
+//! eliminates candidates.
 
+mod fish;
+mod intersections;
+mod singles;
+mod subsets;
+mod wings;
+
+use std::fmt;
+
+use log::{debug, trace};
+use serde::Serialize;
+
+use crate::{
+    SudokuError,
+    types::{
+        Board, CandidateGrid,
+        masks::{DirtyMask, Masks, Word},
+    },
+};
+
+/// (row, col), 0-based
+pub type Cell = (usize, usize);
+
+/// Solving techniques, declared from easiest to hardest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Technique {
+    NakedSingle,
+    HiddenSingle,
+    PointingPair,
+    BoxLineReduction,
+    NakedPair,
+    HiddenPair,
+    NakedTriple,
+    HiddenTriple,
+    NakedQuad,
+    HiddenQuad,
+    XWing,
+    Swordfish,
+    XYWing,
+}
+
+impl Technique {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Technique::NakedSingle => "Naked Single",
+            Technique::HiddenSingle => "Hidden Single",
+            Technique::PointingPair => "Pointing Pair",
+            Technique::BoxLineReduction => "Box/Line Reduction",
+            Technique::NakedPair => "Naked Pair",
+            Technique::HiddenPair => "Hidden Pair",
+            Technique::NakedTriple => "Naked Triple",
+            Technique::HiddenTriple => "Hidden Triple",
+            Technique::NakedQuad => "Naked Quad",
+            Technique::HiddenQuad => "Hidden Quad",
+            Technique::XWing => "X-Wing",
+            Technique::Swordfish => "Swordfish",
+            Technique::XYWing => "XY-Wing",
+        }
+    }
+}
+
+impl fmt::Display for Technique {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// What a deduction changes on the board
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    Place { cell: Cell, digit: u8 },
+    Eliminate { candidates: Vec<(Cell, u8)> },
+}
+
+/// One logical step: the technique, the cells forming its pattern, and its effect
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deduction {
+    pub technique: Technique,
+    pub cells: Vec<Cell>,
+    pub action: Action,
+}
+
+impl fmt::Display for Deduction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.technique)?;
+        match &self.action {
+            Action::Place { cell, digit } => {
+                write!(f, "r{}c{} = {}", cell.0 + 1, cell.1 + 1, digit)
+            }
+            Action::Eliminate { candidates } => {
+                for (idx, ((r, c), digit)) in candidates.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "r{}c{} <> {}", r + 1, c + 1, digit)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A row, column, box or X-Sudoku diagonal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Row(usize),
+    Col(usize),
+    Box(usize),
+    /// 0 = main (r == c), 1 = anti (r + c == N - 1)
+    Diagonal(usize),
+}
+
+impl Unit {
+    /// Cells of the unit, boxes taken as the classic KxK boxes
+    /// (`Masks::unit_cells` follows the board's regions instead)
+    pub fn cells<const N: usize>(self) -> [Cell; N] {
+        let k = N.isqrt();
+        std::array::from_fn(|i| match self {
+            Unit::Row(r) => (r, i),
+            Unit::Col(c) => (i, c),
+            Unit::Box(b) => ((b / k) * k + i / k, (b % k) * k + i % k),
+            Unit::Diagonal(0) => (i, i),
+            Unit::Diagonal(_) => (i, N - 1 - i),
+        })
+    }
+
+    pub fn all<const N: usize>() -> impl Iterator<Item = Unit> {
+        (0..N)
+            .map(Unit::Row)
+            .chain((0..N).map(Unit::Col))
+            .chain((0..N).map(Unit::Box))
+    }
+}
+
+/// Digits (1-based) whose bits are set in `mask`
+pub fn digits(mut mask: Word) -> impl Iterator<Item = u8> {
+    std::iter::from_fn(move || {
+        if mask == 0 {
+            None
+        } else {
+            let digit = mask.trailing_zeros() as u8 + 1;
+            mask &= mask - 1;
+            Some(digit)
+        }
+    })
+}
+
+/// Call `f` on every k-subset of `0..n` until it returns `Some`
+fn find_combination<R>(n: usize, k: usize, f: &mut impl FnMut(&[usize]) -> Option<R>) -> Option<R> {
+    fn recurse<R>(
+        start: usize,
+        n: usize,
+        k: usize,
+        picked: &mut Vec<usize>,
+        f: &mut impl FnMut(&[usize]) -> Option<R>,
+    ) -> Option<R> {
+        if picked.len() == k {
+            return f(picked);
+        }
+        for idx in start..n {
+            picked.push(idx);
+            let found = recurse(idx + 1, n, k, picked, f);
+            picked.pop();
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+    recurse(0, n, k, &mut Vec::with_capacity(k), f)
+}
+
+/// Board plus per-cell candidate state for logical solving
+#[derive(Debug, Clone)]
+pub struct LogicBoard<const N: usize> {
+    pub board: Board<N>,
+    /// Digits ruled out per cell (`Masks::conflict`) and the units they follow
+    masks: Masks<N>,
+}
+
+impl<const N: usize> LogicBoard<N> {
+    pub fn new(board: Board<N>) -> Result<Self, SudokuError> {
+        Self::new_with_rules(board, &Masks::default())
+    }
+
+    /// Logic board under the variant settings of `rules` (X-Sudoku diagonals,
+    /// jigsaw regions); the digits recorded in `rules` are ignored
+    pub fn new_with_rules(board: Board<N>, rules: &Masks<N>) -> Result<Self, SudokuError> {
+        let mut masks = rules.cleared();
+        masks.generate(&board)?;
+        Ok(Self { board, masks })
+    }
+
+    /// Start from user-supplied pencil marks instead of the givens alone.
+    ///
+    /// Cells with a single candidate are taken as filled, and the digits placed
+    /// in a cell's row, column and box are ruled out on top of its marks.
+    pub fn from_candidates(candidates: &CandidateGrid<N>) -> Result<Self, SudokuError> {
+        let board = candidates.to_board();
+        let mut masks = Masks::<N>::default();
+        masks.generate(&board)?;
+        masks.restrict(candidates);
+        Ok(Self { board, masks })
+    }
+
+    /// Current pencil marks, filled cells holding their digit only
+    pub fn candidate_grid(&self) -> CandidateGrid<N> {
+        CandidateGrid::from_fn(|(r, c)| match self.board.cells[r][c] {
+            0 => self.candidates((r, c)),
+            digit => 1 << (digit - 1),
+        })
+    }
+
+    /// Candidate bits of an empty cell (bit d-1 = digit d), 0 for filled cells
+    #[inline]
+    pub fn candidates(&self, (r, c): Cell) -> Word {
+        if self.board.cells[r][c] != 0 {
+            return 0;
+        }
+        DirtyMask::<N>::FULL & !*self.masks.conflict[r][c].get()
+    }
+
+    #[inline]
+    pub fn has_candidate(&self, cell: Cell, digit: u8) -> bool {
+        self.candidates(cell) & (1 << (digit - 1)) != 0
+    }
+
+    /// Whether `digit` is already placed somewhere in `cells`
+    fn is_placed(&self, cells: &[Cell], digit: u8) -> bool {
+        cells.iter().any(|&(r, c)| self.board.cells[r][c] == digit)
+    }
+
+    /// Units the board's rules constrain
+    pub(super) fn units(&self) -> impl Iterator<Item = Unit> + use<N> {
+        self.masks.units()
+    }
+
+    #[inline]
+    pub(super) fn unit_cells(&self, unit: Unit) -> [Cell; N] {
+        self.masks.unit_cells(unit)
+    }
+
+    fn place(&mut self, (r, c): Cell, digit: u8) {
+        self.board.cells[r][c] = digit;
+        for unit in self.masks.cell_units(r, c) {
+            for (pr, pc) in self.unit_cells(unit) {
+                self.masks.conflict[pr][pc].dirty_set(digit as usize);
+            }
+        }
+    }
+
+    fn eliminate(&mut self, (r, c): Cell, digit: u8) {
+        self.masks.conflict[r][c].dirty_set(digit as usize);
+    }
+
+    pub fn apply(&mut self, deduction: &Deduction) {
+        trace!("Applying {}", deduction);
+        match &deduction.action {
+            Action::Place { cell, digit } => self.place(*cell, *digit),
+            Action::Eliminate { candidates } => {
+                for &(cell, digit) in candidates {
+                    self.eliminate(cell, digit);
+                }
+            }
+        }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.board.cells.iter().flatten().all(|&value| value != 0)
+    }
+
+    /// An empty cell without candidates, or a unit where a missing digit has nowhere to go
+    pub fn has_contradiction(&self) -> bool {
+        let dead_cell = (0..N)
+            .any(|r| (0..N).any(|c| self.board.cells[r][c] == 0 && self.candidates((r, c)) == 0));
+        dead_cell
+            || self.units().any(|unit| {
+                let cells = self.unit_cells(unit);
+                (1..=N as u8).any(|digit| {
+                    !self.is_placed(&cells, digit)
+                        && !cells.iter().any(|&cell| self.has_candidate(cell, digit))
+                })
+            })
+    }
+
+    /// Easiest deduction available in the current state
+    pub fn next_deduction(&self) -> Option<Deduction> {
+        self.naked_single()
+            .or_else(|| self.hidden_single())
+            .or_else(|| self.pointing())
+            .or_else(|| self.box_line_reduction())
+            .or_else(|| self.naked_subset(2))
+            .or_else(|| self.hidden_subset(2))
+            .or_else(|| self.naked_subset(3))
+            .or_else(|| self.hidden_subset(3))
+            .or_else(|| self.naked_subset(4))
+            .or_else(|| self.hidden_subset(4))
+            .or_else(|| self.fish(2))
+            .or_else(|| self.fish(3))
+            .or_else(|| self.xy_wing())
+    }
+
+    /// Apply deductions until the board is solved or no technique applies.
+    ///
+    /// Returns every step taken, in order.
+    pub fn solve(&mut self) -> Vec<Deduction> {
+        let mut steps = Vec::new();
+        while !self.is_solved() && !self.has_contradiction() {
+            let Some(deduction) = self.next_deduction() else {
+                break;
+            };
+            self.apply(&deduction);
+            steps.push(deduction);
+        }
+        debug!(
+            "Logic solver took {} step(s), solved={}",
+            steps.len(),
+            self.is_solved()
+        );
+        steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SolveOutcome, SudokuSolver};
+
+    fn unique_solution<const N: usize, const K: usize>(board: Board<N>) -> Board<N> {
+        solution_with_rules::<N, K>(board, &Masks::default())
+    }
+
+    fn solution_with_rules<const N: usize, const K: usize>(
+        board: Board<N>,
+        rules: &Masks<N>,
+    ) -> Board<N> {
+        let solver = SudokuSolver::<N, K>::new_with_rules(board, rules).unwrap();
+        match solver.solve().outcome {
+            SolveOutcome::Unique(solution) => solution,
+            outcome => panic!("expected a unique solution, got {}", outcome.status()),
+        }
+    }
+
+    /// Every placement must match the solution and no elimination may remove it
+    fn assert_sound<const N: usize, const K: usize>(
+        board: Board<N>,
+        rules: &Masks<N>,
+    ) -> LogicBoard<N> {
+        let solution = solution_with_rules::<N, K>(board, rules);
+        let mut logic = LogicBoard::new_with_rules(board, rules).unwrap();
+        for step in logic.solve() {
+            match step.action {
+                Action::Place { cell, digit } => {
+                    assert_eq!(solution.cells[cell.0][cell.1], digit, "{step}")
+                }
+                Action::Eliminate { candidates } => {
+                    assert!(!candidates.is_empty());
+                    for ((r, c), digit) in candidates {
+                        assert_ne!(solution.cells[r][c], digit, "{}", step.technique);
+                    }
+                }
+            }
+        }
+        logic
+    }
+
+    #[test]
+    fn test_easy_9x9_solved_by_singles() {
+        let board = Board::<9>::parse(include_str!("../../../dataset/input.txt")).unwrap();
+        let mut logic = LogicBoard::new(board).unwrap();
+        let steps = logic.solve();
+
+        assert!(logic.is_solved());
+        assert_eq!(logic.board, unique_solution::<9, 3>(board));
+        assert!(
+            steps
+                .iter()
+                .all(|step| step.technique <= Technique::HiddenSingle)
+        );
+    }
+
+    #[test]
+    fn test_deductions_are_sound() {
+        let hard = Board::<9>::from_line(include_str!("../../../benches/fixtures/hard_9x9.txt"));
+        assert_sound::<9, 3>(hard.unwrap(), &Masks::default());
+
+        let large =
+            Board::<16>::from_line(include_str!("../../../benches/fixtures/unique_16x16.txt"));
+        assert!(assert_sound::<16, 4>(large.unwrap(), &Masks::default()).is_solved());
+    }
+
+    #[test]
+    fn test_x_wing() {
+        // Classic X-Wing on digit 7 in rows 2 and 8 (columns 2 and 9)
+        let board = Board::<9>::from_line(
+            "1.....569492.561.8.561.924...964.8.1.64.1....218.356.4.4.5...169.5.614.2621.....5",
+        )
+        .unwrap();
+        let solution = unique_solution::<9, 3>(board);
+        let mut logic = LogicBoard::new(board).unwrap();
+        let mut techniques = Vec::new();
+        while let Some(step) = logic.next_deduction() {
+            if let Action::Eliminate { candidates } = &step.action {
+                for &((r, c), digit) in candidates {
+                    assert_ne!(solution.cells[r][c], digit);
+                }
+            }
+            techniques.push(step.technique);
+            logic.apply(&step);
+        }
+        assert!(logic.is_solved());
+        assert!(techniques.contains(&Technique::XWing), "{techniques:?}");
+    }
+
+    #[test]
+    fn test_x_sudoku_diagonals() {
+        // Has 26 solutions without the diagonals
+        let board =
+            Board::<9>::from_line(include_str!("../../../benches/fixtures/x_9x9.txt")).unwrap();
+        let rules = Masks {
+            diagonal: true,
+            ..Default::default()
+        };
+        let logic = assert_sound::<9, 3>(board, &rules);
+        assert!(logic.is_solved());
+
+        let mut plain = LogicBoard::new(board).unwrap();
+        plain.solve();
+        assert!(!plain.is_solved());
+    }
+}
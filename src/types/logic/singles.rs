@@ -0,0 +1,41 @@
+use super::{Action, Deduction, LogicBoard, Technique};
+
+impl<const N: usize> LogicBoard<N> {
+    /// A cell with exactly one candidate left
+    pub(super) fn naked_single(&self) -> Option<Deduction> {
+        (0..N)
+            .flat_map(|r| (0..N).map(move |c| (r, c)))
+            .find(|&cell| self.candidates(cell).count_ones() == 1)
+            .map(|cell| Deduction {
+                technique: Technique::NakedSingle,
+                cells: vec![cell],
+                action: Action::Place {
+                    cell,
+                    digit: self.candidates(cell).trailing_zeros() as u8 + 1,
+                },
+            })
+    }
+
+    /// A digit that fits in only one cell of a unit
+    pub(super) fn hidden_single(&self) -> Option<Deduction> {
+        for unit in self.units() {
+            let cells = self.unit_cells(unit);
+            for digit in 1..=N as u8 {
+                if self.is_placed(&cells, digit) {
+                    continue;
+                }
+                let mut spots = cells
+                    .iter()
+                    .filter(|&&cell| self.has_candidate(cell, digit));
+                if let (Some(&cell), None) = (spots.next(), spots.next()) {
+                    return Some(Deduction {
+                        technique: Technique::HiddenSingle,
+                        cells: cells.to_vec(),
+                        action: Action::Place { cell, digit },
+                    });
+                }
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,119 @@
+use super::{Action, Cell, Deduction, LogicBoard, Technique, digits, find_combination};
+use crate::types::masks::Word;
+
+fn naked_technique(size: usize) -> Technique {
+    match size {
+        2 => Technique::NakedPair,
+        3 => Technique::NakedTriple,
+        _ => Technique::NakedQuad,
+    }
+}
+
+fn hidden_technique(size: usize) -> Technique {
+    match size {
+        2 => Technique::HiddenPair,
+        3 => Technique::HiddenTriple,
+        _ => Technique::HiddenQuad,
+    }
+}
+
+impl<const N: usize> LogicBoard<N> {
+    /// `size` cells of a unit whose candidates together span exactly `size`
+    /// digits; those digits leave the unit's other cells
+    pub(super) fn naked_subset(&self, size: usize) -> Option<Deduction> {
+        for unit in self.units() {
+            let empty: Vec<Cell> = self
+                .unit_cells(unit)
+                .into_iter()
+                .filter(|&cell| self.candidates(cell) != 0)
+                .collect();
+            if empty.len() <= size {
+                continue;
+            }
+            // Subset cells are the ones holding 2..=size candidates
+            let pool: Vec<Cell> = empty
+                .iter()
+                .copied()
+                .filter(|&cell| (2..=size as u32).contains(&self.candidates(cell).count_ones()))
+                .collect();
+
+            let found = find_combination(pool.len(), size, &mut |picked| {
+                let subset: Vec<Cell> = picked.iter().map(|&idx| pool[idx]).collect();
+                let union = subset
+                    .iter()
+                    .fold(0, |acc: Word, &cell| acc | self.candidates(cell));
+                if union.count_ones() as usize != size {
+                    return None;
+                }
+                let candidates: Vec<_> = empty
+                    .iter()
+                    .filter(|cell| !subset.contains(cell))
+                    .flat_map(|&cell| {
+                        digits(self.candidates(cell) & union).map(move |digit| (cell, digit))
+                    })
+                    .collect();
+                (!candidates.is_empty()).then(|| Deduction {
+                    technique: naked_technique(size),
+                    cells: subset,
+                    action: Action::Eliminate { candidates },
+                })
+            });
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+
+    /// `size` digits of a unit that only fit in the same `size` cells; every
+    /// other candidate leaves those cells
+    pub(super) fn hidden_subset(&self, size: usize) -> Option<Deduction> {
+        for unit in self.units() {
+            let cells = self.unit_cells(unit);
+            // Positions of each unplaced digit as a bitmask over the unit's cells
+            let positions: Vec<(u8, Word)> = (1..=N as u8)
+                .filter_map(|digit| {
+                    let spots = cells
+                        .iter()
+                        .enumerate()
+                        .filter(|&(_, &cell)| self.has_candidate(cell, digit))
+                        .fold(0, |acc: Word, (idx, _)| acc | 1 << idx);
+                    (2..=size as u32)
+                        .contains(&spots.count_ones())
+                        .then_some((digit, spots))
+                })
+                .collect();
+
+            let found = find_combination(positions.len(), size, &mut |picked| {
+                let union = picked
+                    .iter()
+                    .fold(0, |acc: Word, &idx| acc | positions[idx].1);
+                if union.count_ones() as usize != size {
+                    return None;
+                }
+                let keep = picked
+                    .iter()
+                    .fold(0, |acc: Word, &idx| acc | 1 << (positions[idx].0 - 1));
+                let subset: Vec<Cell> = (0..N)
+                    .filter(|idx| union & (1 << idx) != 0)
+                    .map(|idx| cells[idx])
+                    .collect();
+                let candidates: Vec<_> = subset
+                    .iter()
+                    .flat_map(|&cell| {
+                        digits(self.candidates(cell) & !keep).map(move |digit| (cell, digit))
+                    })
+                    .collect();
+                (!candidates.is_empty()).then(|| Deduction {
+                    technique: hidden_technique(size),
+                    cells: subset,
+                    action: Action::Eliminate { candidates },
+                })
+            });
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,60 @@
+use super::{Action, Cell, Deduction, LogicBoard, Technique, digits};
+
+impl<const N: usize> LogicBoard<N> {
+    /// XY-Wing: a pivot `xy` sees two pincers `xz` and `yz`, so whichever
+    /// digit the pivot takes, one pincer is `z` and every cell seeing both
+    /// pincers loses `z`
+    pub(super) fn xy_wing(&self) -> Option<Deduction> {
+        let bivalue: Vec<Cell> = (0..N)
+            .flat_map(|r| (0..N).map(move |c| (r, c)))
+            .filter(|&cell| self.candidates(cell).count_ones() == 2)
+            .collect();
+
+        for &pivot in &bivalue {
+            let pivot_mask = self.candidates(pivot);
+            let wings: Vec<Cell> = bivalue
+                .iter()
+                .copied()
+                .filter(|&wing| {
+                    self.masks.sees(pivot, wing)
+                        && (self.candidates(wing) & pivot_mask).count_ones() == 1
+                })
+                .collect();
+
+            for (idx, &first) in wings.iter().enumerate() {
+                for &second in &wings[idx + 1..] {
+                    let (a, b) = (self.candidates(first), self.candidates(second));
+                    let shared = a & b;
+                    // Pincers share only z, and together cover both pivot digits
+                    if shared.count_ones() != 1
+                        || shared & pivot_mask != 0
+                        || (a | b) & pivot_mask != pivot_mask
+                    {
+                        continue;
+                    }
+                    let Some(z) = digits(shared).next() else {
+                        continue;
+                    };
+                    let candidates: Vec<_> = (0..N)
+                        .flat_map(|r| (0..N).map(move |c| (r, c)))
+                        .filter(|&cell| {
+                            cell != pivot
+                                && self.masks.sees(cell, first)
+                                && self.masks.sees(cell, second)
+                                && self.has_candidate(cell, z)
+                        })
+                        .map(|cell| (cell, z))
+                        .collect();
+                    if !candidates.is_empty() {
+                        return Some(Deduction {
+                            technique: Technique::XYWing,
+                            cells: vec![pivot, first, second],
+                            action: Action::Eliminate { candidates },
+                        });
+                    }
+                }
+            }
+        }
+        None
+    }
+}
@@ -3,32 +3,54 @@ use std::{
     ops::{BitAnd, BitOr, BitOrAssign},
 };
 
+/// Backing word of every mask.
+///
+/// Stable const generics cannot pick a storage type from `N`, so a single
+/// 64-bit word covers every board up to 64x64 (K = 8).
+pub type Word = u64;
+
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
 pub struct BitString<const N: usize> {
-    bits: u32,
+    bits: Word,
 }
 
 impl<const N: usize> BitString<N> {
+    /// Bits 0 to N-1 set, e.g. N=4 -> 0b0000_1111
+    pub const FULL: Word = {
+        assert!(N <= Word::BITS as usize, "BitString holds at most 64 bits");
+        // Shifting by the full word width would overflow, hence the N == 64 case
+        if N == Word::BITS as usize {
+            Word::MAX
+        } else {
+            (1 << N) - 1
+        }
+    };
+
+    /// Mask of the given bits, anything from bit N upwards dropped
+    pub fn from_bits(bits: Word) -> Self {
+        BitString {
+            bits: bits & Self::FULL,
+        }
+    }
+
     pub fn is_all_set(&self) -> bool {
-        // Check if all bits from.bits to N-1 are set
-        // example: N=4 ->.bitsb0000_1111 = (1 << 4) - 1 = 15
-        self.bits == (1 << N) - 1
+        self.bits == Self::FULL
     }
 
     // For logical deduction (mark unit dirty)
     #[inline(always)]
     pub fn set(&mut self, idx: usize) -> &Self {
-        self.bits |= 1u32 << idx;
+        self.bits |= 1 << idx;
         self
     }
 
     // For logical deduction (mark unit clean)
     #[inline(always)]
     pub fn reset(&mut self, idx: usize) {
-        self.bits &= !(1u32 << idx);
+        self.bits &= !(1 << idx);
     }
 
-    pub fn get(&self) -> &u32 {
+    pub fn get(&self) -> &Word {
         &self.bits
     }
 
@@ -39,7 +61,7 @@ impl<const N: usize> BitString<N> {
 
     #[inline(always)]
     pub fn is_set(&self, i: usize) -> bool {
-        (self.bits & (1u32 << i)) != 0
+        (self.bits & (1 << i)) != 0
     }
 }
 
@@ -76,7 +98,7 @@ pub type EmptyMask<const N: usize> = BitString<N>;
 impl<const N: usize> EmptyMask<N> {
     #[inline(always)]
     pub fn set_value(&mut self, idx: usize, value: u8) {
-        self.bits |= ((value == 0) as u32) << idx;
+        self.bits |= ((value == 0) as Word) << idx;
     }
 }
 /// Iterator using Kernighan's trick
@@ -111,3 +133,41 @@ impl<const N: usize> DirtyMask<N> {
         self.bits & rhs.bits != 0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{helper::pattern_values, solve_dyn};
+
+    #[test]
+    fn test_masks_beyond_32_bits() {
+        let mut mask = DirtyMask::<64>::default();
+        for num in 1..=64 {
+            assert!(!mask.is_all_set());
+            mask.dirty_set(num);
+        }
+        assert!(mask.is_all_set());
+        assert!(mask.is_dirty(64));
+
+        let mut empty = EmptyMask::<36>::default();
+        empty.set_value(35, 0);
+        empty.set_value(33, 0);
+        empty.set_value(0, 7);
+        assert_eq!(empty.collect::<Vec<_>>(), vec![33, 35]);
+    }
+
+    #[test]
+    fn test_solve_36x36() {
+        const N: usize = 36;
+        // Pattern-based complete grid with a few holes punched in
+        let solved = pattern_values(N);
+        let mut cells = solved.clone();
+        for idx in [0, 7, 100, 555, 1295] {
+            cells[idx] = 0;
+        }
+
+        let report = solve_dyn(&cells, None).unwrap();
+        assert_eq!(report.size(), 36);
+        assert_eq!(report.solutions(), vec![solved]);
+    }
+}
@@ -1,14 +1,27 @@
 use log::{debug, trace};
 
-use crate::types::Board;
+use crate::{
+    SudokuError,
+    types::{
+        Board, CandidateGrid, Regions,
+        logic::{Cell, Unit},
+    },
+};
 mod bitstring;
-pub use bitstring::{DirtyMask, EmptyMask};
+pub use bitstring::{DirtyMask, EmptyMask, Word};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Masks<const N: usize> {
+    /// One mask per region (the KxK boxes unless `regions` says otherwise)
     pub boxs: [DirtyMask<N>; N],
     pub rows: [DirtyMask<N>; N],
     pub cols: [DirtyMask<N>; N],
+    /// Main and anti diagonal, only filled for X-Sudoku
+    pub diags: [DirtyMask<N>; 2],
+    /// Whether both main diagonals must hold every digit once (X-Sudoku)
+    pub diagonal: bool,
+    /// Cells of every minigrid
+    pub regions: Regions<N>,
     pub conflict: [[DirtyMask<N>; N]; N],
 }
 
@@ -18,6 +31,9 @@ impl<const N: usize> Default for Masks<N> {
             boxs: [DirtyMask::default(); N],
             rows: [DirtyMask::default(); N],
             cols: [DirtyMask::default(); N],
+            diags: [DirtyMask::default(); 2],
+            diagonal: false,
+            regions: Regions::default(),
             conflict: [[DirtyMask::default(); N]; N],
         }
     }
@@ -26,30 +42,96 @@ impl<const N: usize> Default for Masks<N> {
 impl<const N: usize> Masks<N> {
     const K: usize = super::Board::<N>::K;
 
-    pub fn generate(&mut self, board: &super::Board<N>) {
+    /// Empty masks with the same diagonal setting and regions
+    pub fn cleared(&self) -> Self {
+        Masks {
+            diagonal: self.diagonal,
+            regions: self.regions.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Diagonals through a cell that this variant constrains
+    pub fn cell_diagonals(&self, r: usize, c: usize) -> impl Iterator<Item = usize> + use<N> {
+        let diagonal = self.diagonal;
+        Board::<N>::diagonals(r, c).filter(move |_| diagonal)
+    }
+
+    /// Units whose digits must all differ: rows, columns, regions and, for
+    /// X-Sudoku, both diagonals
+    pub fn units(&self) -> impl Iterator<Item = Unit> + use<N> {
+        let diagonals = if self.diagonal { 0..2 } else { 0..0 };
+        Unit::all::<N>().chain(diagonals.map(Unit::Diagonal))
+    }
+
+    /// Cells of `unit`, boxes following `regions`
+    pub fn unit_cells(&self, unit: Unit) -> [Cell; N] {
+        match unit {
+            Unit::Box(b) => *self.regions.cells(b),
+            _ => unit.cells::<N>(),
+        }
+    }
+
+    /// Constrained units through a cell
+    pub fn cell_units(&self, r: usize, c: usize) -> impl Iterator<Item = Unit> + use<N> {
+        [
+            Unit::Row(r),
+            Unit::Col(c),
+            Unit::Box(self.regions.region(r, c)),
+        ]
+        .into_iter()
+        .chain(self.cell_diagonals(r, c).map(Unit::Diagonal))
+    }
+
+    /// Whether two distinct cells share a constrained unit
+    pub fn sees(&self, (ar, ac): Cell, (br, bc): Cell) -> bool {
+        (ar, ac) != (br, bc)
+            && self
+                .cell_units(ar, ac)
+                .any(|unit| self.cell_units(br, bc).any(|other| other == unit))
+    }
+
+    /// Filled cells of `board` sharing their digit with another cell of a
+    /// constrained unit, as sorted (row, col) positions
+    pub fn conflicting_cells(&self, board: &Board<N>) -> Vec<Cell> {
+        board.conflicts_in(self.units().map(|unit| self.unit_cells(unit)))
+    }
+
+    pub fn generate(&mut self, board: &super::Board<N>) -> Result<(), SudokuError> {
         debug!("Board size: {}x{}, Box size: {}x{}", N, N, Self::K, Self::K);
 
         for (r, row) in board.cells.iter().enumerate() {
             for (c, &val) in row.iter().enumerate() {
                 if val != 0 {
                     let val = val as usize;
-                    let b = Board::<N>::box_idx(r, c);
+                    let b = self.regions.region(r, c);
                     trace!("Cell ({}, {}): val={}", r, c, val);
 
                     if self.rows[r].is_dirty(val)
                         || self.cols[c].is_dirty(val)
                         || self.boxs[b].is_dirty(val)
+                        || self
+                            .cell_diagonals(r, c)
+                            .any(|d| self.diags[d].is_dirty(val))
                     {
                         trace!(
                             "Conflict detected! self.rows={}, self.cols={}, self.boxs={}",
                             self.rows[r], self.cols[c], self.boxs[b]
                         );
-                        panic!("Invalid board: duplicate value found");
+                        return Err(SudokuError::Conflict {
+                            row: r,
+                            col: c,
+                            box_idx: b,
+                            digit: val as u8,
+                        });
                     }
 
                     self.rows[r].dirty_set(val);
                     self.cols[c].dirty_set(val);
                     self.boxs[b].dirty_set(val);
+                    for d in self.cell_diagonals(r, c) {
+                        self.diags[d].dirty_set(val);
+                    }
                     trace!(
                         "Updated masks: self.rows[{}]={}, self.cols[{}]={}, self.boxs[{}]={}",
                         r, self.rows[r], c, self.cols[c], b, self.boxs[b],
@@ -58,12 +140,26 @@ impl<const N: usize> Masks<N> {
             }
         }
 
-        for (r, row) in self.conflict.iter_mut().enumerate() {
-            for (c, val) in row.iter_mut().enumerate() {
-                let b = Board::<N>::box_idx(r, c);
-                *val = self.rows[r] | self.cols[c] | self.boxs[b];
+        for r in 0..N {
+            for c in 0..N {
+                let b = self.regions.region(r, c);
+                let mut val = self.rows[r] | self.cols[c] | self.boxs[b];
+                for d in self.cell_diagonals(r, c) {
+                    val |= self.diags[d];
+                }
+                self.conflict[r][c] = val;
                 trace!("Updated self.conflict[{}][{}]={}", r, c, val);
             }
         }
+        Ok(())
+    }
+
+    /// Rule out, in every cell, the digits `candidates` does not allow
+    pub fn restrict(&mut self, candidates: &CandidateGrid<N>) {
+        for (r, row) in self.conflict.iter_mut().enumerate() {
+            for (c, conflict) in row.iter_mut().enumerate() {
+                *conflict |= candidates.eliminated((r, c));
+            }
+        }
     }
 }
@@ -1,34 +1,42 @@
-use crate::types::masks::EmptyMask;
+use crate::types::{logic::Cell, masks::EmptyMask};
 
 use super::Board;
 
 #[derive(Debug)]
 pub struct Minigrid<const N: usize, const K: usize> {
     pub id: usize,
-    pub cells: [u8; N],      // Flattened KxK = N
+    pub cells: [u8; N],      // Flattened region cells, KxK for square boxes
     pub empty: EmptyMask<N>, // Bitmask of empty cells
+    /// Board position of every cell, in row-major order
+    pub positions: [Cell; N],
 }
 
 impl<const N: usize, const K: usize> Minigrid<N, K> {
     pub const K: usize = N.isqrt();
 
+    /// Minigrid of the KxK box `id`
     pub fn new(id: usize, board: &Board<N>) -> Self {
-        let mut cells = [0u8; N];
         let start_row = (id / Self::K) * Self::K;
         let start_col = (id % Self::K) * Self::K;
+        let positions =
+            std::array::from_fn(|idx| (start_row + idx / Self::K, start_col + idx % Self::K));
+        Self::from_region(id, board, &positions)
+    }
+
+    /// Minigrid of an arbitrary region given by its cells
+    pub fn from_region(id: usize, board: &Board<N>, positions: &[Cell; N]) -> Self {
+        let mut cells = [0u8; N];
         let mut empty_mask = EmptyMask::default();
-        for i in 0..Self::K {
-            for j in 0..Self::K {
-                let idx = i * Self::K + j;
-                let value = board.cells[start_row + i][start_col + j];
-                cells[idx] = value;
-                empty_mask.set_value(idx, value);
-            }
+        for (idx, &(r, c)) in positions.iter().enumerate() {
+            let value = board.cells[r][c];
+            cells[idx] = value;
+            empty_mask.set_value(idx, value);
         }
         Self {
             id,
             cells,
             empty: empty_mask,
+            positions: *positions,
         }
     }
 }
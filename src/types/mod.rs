@@ -1,8 +1,14 @@
 pub mod board;
+pub mod candidates;
+pub mod format;
 pub mod graph;
 pub mod logic;
 pub mod masks;
 pub mod minigrid;
+pub mod regions;
 
 pub use board::Board;
+pub use candidates::CandidateGrid;
+pub use format::Format;
 pub use minigrid::Minigrid;
+pub use regions::Regions;
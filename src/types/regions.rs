@@ -0,0 +1,286 @@
+//! Region maps: which cells form each "minigrid".
+//!
+//! Classic boards use the KxK boxes; jigsaw boards split the grid into N
+//! arbitrary connected regions of N cells each.
+
+use log::debug;
+
+use crate::{
+    SudokuError,
+    types::{
+        Board,
+        logic::{Cell, Unit},
+        masks::Word,
+    },
+};
+
+/// Region id of every cell plus the derived per-region geometry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regions<const N: usize> {
+    ids: [[u8; N]; N],
+    /// Cells of every region in row-major order
+    cells: Vec<[Cell; N]>,
+    /// Rows (bit r) and columns (bit c) every region touches
+    lines: Vec<(Word, Word)>,
+    /// Whether every region crosses the main (0) and anti (1) diagonal
+    diagonals: Vec<[bool; 2]>,
+    square: bool,
+}
+
+impl<const N: usize> Default for Regions<N> {
+    /// The KxK boxes of a classic board
+    fn default() -> Self {
+        let k = N.isqrt().max(1);
+        // Sizes without KxK boxes are rejected elsewhere; keep the ids in range anyway
+        let ids =
+            std::array::from_fn(|r| std::array::from_fn(|c| (((r / k) * k + c / k) % N) as u8));
+        Self::build(ids, true)
+    }
+}
+
+impl<const N: usize> Regions<N> {
+    /// Region map from one id in `0..N` per cell.
+    ///
+    /// Every region must hold exactly N orthogonally connected cells.
+    pub fn new(ids: [[u8; N]; N]) -> Result<Self, SudokuError> {
+        let mut sizes = [0; N];
+        for &id in ids.iter().flatten() {
+            if id as usize >= N {
+                return Err(SudokuError::RegionCount {
+                    expected: N,
+                    found: id as usize + 1,
+                });
+            }
+            sizes[id as usize] += 1;
+        }
+        if let Some(region) = sizes.iter().position(|&size| size != N) {
+            return Err(SudokuError::RegionSize {
+                region,
+                cells: sizes[region],
+            });
+        }
+
+        let square = ids == Self::default().ids;
+        let regions = Self::build(ids, square);
+        if let Some(region) = (0..N).find(|&id| !regions.is_connected(id)) {
+            return Err(SudokuError::DisconnectedRegion { region });
+        }
+        debug!("Region map accepted (square boxes: {})", square);
+        Ok(regions)
+    }
+
+    /// Read one label per cell, whitespace ignored. Any N distinct characters
+    /// work as labels; ids follow the order in which labels first appear.
+    pub fn parse(input: &str) -> Result<Self, SudokuError> {
+        let labels: Vec<char> = input.chars().filter(|ch| !ch.is_whitespace()).collect();
+        if labels.len() != N * N {
+            return Err(SudokuError::CellCount {
+                expected: N * N,
+                found: labels.len(),
+            });
+        }
+
+        let mut distinct: Vec<char> = Vec::with_capacity(N);
+        for &label in &labels {
+            if !distinct.contains(&label) {
+                distinct.push(label);
+            }
+        }
+        if distinct.len() != N {
+            return Err(SudokuError::RegionCount {
+                expected: N,
+                found: distinct.len(),
+            });
+        }
+
+        let mut ids = [[0u8; N]; N];
+        for (idx, label) in labels.iter().enumerate() {
+            let id = distinct
+                .iter()
+                .position(|other| other == label)
+                .unwrap_or(0);
+            ids[idx / N][idx % N] = id as u8;
+        }
+        Self::new(ids)
+    }
+
+    fn build(ids: [[u8; N]; N], square: bool) -> Self {
+        let mut cells = vec![[(0, 0); N]; N];
+        let mut filled = [0; N];
+        let mut lines = vec![(0, 0); N];
+        let mut diagonals = vec![[false; 2]; N];
+        for (r, row) in ids.iter().enumerate() {
+            for (c, &id) in row.iter().enumerate() {
+                let id = id as usize;
+                if let Some(cell) = cells[id].get_mut(filled[id]) {
+                    *cell = (r, c);
+                }
+                filled[id] += 1;
+                lines[id].0 |= 1 << r;
+                lines[id].1 |= 1 << c;
+                for d in Board::<N>::diagonals(r, c) {
+                    diagonals[id][d] = true;
+                }
+            }
+        }
+        Self {
+            ids,
+            cells,
+            lines,
+            diagonals,
+            square,
+        }
+    }
+
+    fn is_connected(&self, id: usize) -> bool {
+        let cells = &self.cells[id];
+        let mut reached = vec![cells[0]];
+        let mut next = 0;
+        while let Some(&(r, c)) = reached.get(next) {
+            next += 1;
+            for neighbour in [
+                (r.wrapping_sub(1), c),
+                (r + 1, c),
+                (r, c.wrapping_sub(1)),
+                (r, c + 1),
+            ] {
+                if cells.contains(&neighbour) && !reached.contains(&neighbour) {
+                    reached.push(neighbour);
+                }
+            }
+        }
+        reached.len() == N
+    }
+
+    /// Whether the regions are the classic KxK boxes
+    #[inline]
+    pub fn is_square(&self) -> bool {
+        self.square
+    }
+
+    #[inline]
+    pub fn region(&self, r: usize, c: usize) -> usize {
+        self.ids[r][c] as usize
+    }
+
+    /// Cells of region `id` in row-major order, the order of a minigrid's cells
+    #[inline]
+    pub fn cells(&self, id: usize) -> &[Cell; N] {
+        &self.cells[id]
+    }
+
+    /// Whether two distinct regions share a row or a column
+    pub fn share_line(&self, a: usize, b: usize) -> bool {
+        let ((rows_a, cols_a), (rows_b, cols_b)) = (self.lines[a], self.lines[b]);
+        a != b && (rows_a & rows_b != 0 || cols_a & cols_b != 0)
+    }
+
+    /// Main diagonals (0 = main, 1 = anti) crossing region `id`
+    pub fn diagonals(&self, id: usize) -> [bool; 2] {
+        self.diagonals[id]
+    }
+
+    /// Whether no row, column or region of `board` repeats a digit
+    pub fn is_valid(&self, board: &Board<N>) -> bool {
+        let lines = (0..N).flat_map(|i| [Unit::Row(i), Unit::Col(i)].map(Unit::cells::<N>));
+        board
+            .conflicts_in(lines.chain(self.cells.iter().copied()))
+            .is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        SudokuSolver,
+        types::{logic::LogicBoard, masks::Masks},
+    };
+
+    /// Rows 0-1 and 2-3 each split into two interlocking L shapes
+    const JIGSAW_4X4: &str = "aaab abbb cccd cddd";
+
+    #[test]
+    fn test_jigsaw_regions() {
+        let regions = Regions::<4>::parse(JIGSAW_4X4).unwrap();
+        assert!(!regions.is_square());
+        assert_eq!(regions.cells(1), &[(0, 3), (1, 1), (1, 2), (1, 3)]);
+        assert!(regions.share_line(0, 1) && regions.share_line(0, 2));
+        assert!(
+            Regions::<4>::parse("aabb aabb ccdd ccdd")
+                .unwrap()
+                .is_square()
+        );
+
+        assert_eq!(
+            Regions::<4>::parse("aabb abbb cccd cddd"),
+            Err(SudokuError::RegionSize {
+                region: 0,
+                cells: 3
+            })
+        );
+        assert_eq!(
+            Regions::<4>::parse("abab abab cccc dddd"),
+            Err(SudokuError::DisconnectedRegion { region: 0 })
+        );
+
+        // Every jigsaw grid of this layout
+        let empty = Board::<4>::new([[0; 4]; 4]).unwrap();
+        let solver = SudokuSolver::<4, 2>::new_with_regions(empty, regions.clone()).unwrap();
+        let outcome = solver.solve().outcome;
+        assert_eq!(outcome.solution_count(), 144);
+        assert!(
+            outcome
+                .solutions()
+                .iter()
+                .all(|board| regions.is_valid(board))
+        );
+        assert_eq!(solver.count_solutions(None), 144);
+
+        // A box of the classic layout may repeat a digit in a jigsaw
+        let board = Board::<4>::parse("1 0 0 0  0 1 0 0  0 0 0 0  0 0 0 0").unwrap();
+        assert!(SudokuSolver::<4, 2>::new(board).is_err());
+        let solver = SudokuSolver::<4, 2>::new_with_regions(board, regions.clone()).unwrap();
+        let outcome = solver.solve().outcome;
+        assert_eq!(outcome.solution_count(), 6);
+        assert_eq!(solver.count_solutions(None), 6);
+        for solution in outcome.solutions() {
+            assert!(regions.is_valid(solution));
+            assert_eq!(solution.cells[1][1], 1);
+        }
+    }
+
+    #[test]
+    fn test_jigsaw_9x9() {
+        // Classic boxes with two pairs of cells traded across box borders
+        let regions = Regions::<9>::parse(include_str!(
+            "../../benches/fixtures/jigsaw_9x9_regions.txt"
+        ));
+        let regions = regions.unwrap();
+        assert!(!regions.is_square());
+        let board = Board::<9>::from_line(include_str!("../../benches/fixtures/jigsaw_9x9.txt"));
+        let board = board.unwrap();
+        let solution = Board::<9>::from_line(include_str!(
+            "../../benches/fixtures/jigsaw_9x9_solution.txt"
+        ));
+        let solution = solution.unwrap();
+        assert!(regions.is_valid(&solution));
+
+        let rules = Masks {
+            regions: regions.clone(),
+            ..Default::default()
+        };
+        let mut logic = LogicBoard::new_with_rules(board, &rules).unwrap();
+        logic.solve();
+        assert_eq!(logic.board, solution);
+
+        let solver = SudokuSolver::<9, 3>::new_with_regions(board, regions).unwrap();
+        assert_eq!(solver.solve().outcome.solutions(), &[solution]);
+        assert_eq!(solver.count_solutions(None), 1);
+        assert!(solver.is_unique());
+
+        let rating = solver.rate();
+        assert!(rating.solved_by_logic);
+        assert!(rating.score < 60.0);
+    }
+}
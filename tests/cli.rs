@@ -0,0 +1,195 @@
+//! End-to-end checks of the `sudoku_solver` binary.
+
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use solver::{SolveOutcome, SudokuSolver, types::Board};
+
+/// Unique only with both diagonals, 26 solutions without
+const X_PUZZLE: &str = include_str!("../benches/fixtures/x_9x9.txt");
+
+/// Scratch directory for the input files and anything the binary writes
+fn workdir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("sudoku_cli_{}_{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Run the binary in `dir` with warnings-only logging, returning stdout
+fn run(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_sudoku_solver"))
+        .current_dir(dir)
+        .args(["--log-level", "warn", "--input-format", "line"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{:?}: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn write(dir: &Path, name: &str, content: &str) -> String {
+    std::fs::write(dir.join(name), content).unwrap();
+    name.to_string()
+}
+
+#[test]
+fn test_diagonal_flag_reaches_every_command() {
+    let dir = workdir("diagonal");
+    let puzzle = write(&dir, "puzzle.txt", X_PUZZLE);
+
+    // A solution of the plain puzzle that breaks a diagonal
+    let board = Board::<9>::from_line(X_PUZZLE).unwrap();
+    let x_solution = SudokuSolver::<9, 3>::new(board)
+        .unwrap()
+        .with_diagonals(true)
+        .unwrap()
+        .solve()
+        .outcome;
+    let SolveOutcome::Unique(x_solution) = x_solution else {
+        panic!("expected a unique X-Sudoku solution");
+    };
+    let plain = SudokuSolver::<9, 3>::new(board)
+        .unwrap()
+        .solutions()
+        .find(|solution| *solution != x_solution)
+        .unwrap();
+    let progress = write(&dir, "progress.txt", &plain.to_line().unwrap());
+
+    let check = run(&dir, &["check", &puzzle, &progress]);
+    assert!(check.contains("complete: true"), "{check}");
+    let check = run(&dir, &["--diagonal", "check", &puzzle, &progress]);
+    assert!(check.contains("solvable: false"), "{check}");
+    assert!(check.contains("complete: false"), "{check}");
+
+    let candidates = run(&dir, &["--output-format", "line", "candidates", &puzzle]);
+    let x_candidates = run(
+        &dir,
+        &[
+            "--diagonal",
+            "--output-format",
+            "line",
+            "candidates",
+            &puzzle,
+        ],
+    );
+    assert_ne!(candidates, x_candidates);
+
+    let batch = write(&dir, "puzzles.sdm", X_PUZZLE);
+    let results = run(&dir, &["batch", &batch]);
+    assert!(results.starts_with("1 multiple"), "{results}");
+    let results = run(&dir, &["--diagonal", "batch", &batch]);
+    assert!(results.starts_with("1 unique"), "{results}");
+
+    let hint = run(&dir, &["--diagonal", "hint", &puzzle]);
+    assert!(!hint.starts_with("Reveal"), "{hint}");
+
+    let generated = run(
+        &dir,
+        &[
+            "--diagonal",
+            "--output-format",
+            "line",
+            "generate",
+            "--seed",
+            "5",
+            "--clues",
+            "30",
+        ],
+    );
+    let generated = Board::<9>::from_line(generated.trim()).unwrap();
+    let solver = SudokuSolver::<9, 3>::new(generated).unwrap();
+    assert!(solver.with_diagonals(true).unwrap().is_unique());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_regions_flag_reaches_logic_commands() {
+    let dir = workdir("regions");
+    let regions = write(
+        &dir,
+        "regions.txt",
+        include_str!("../benches/fixtures/jigsaw_9x9_regions.txt"),
+    );
+    let puzzle = write(
+        &dir,
+        "puzzle.txt",
+        include_str!("../benches/fixtures/jigsaw_9x9.txt"),
+    );
+    let progress = write(
+        &dir,
+        "progress.txt",
+        include_str!("../benches/fixtures/jigsaw_9x9_solution.txt"),
+    );
+
+    assert_eq!(run(&dir, &["--regions", &regions, "count", &puzzle]), "1\n");
+    let check = run(&dir, &["--regions", &regions, "check", &puzzle, &progress]);
+    assert!(check.contains("complete: true"), "{check}");
+    let hint = run(&dir, &["--regions", &regions, "hint", &puzzle]);
+    assert!(!hint.starts_with("Reveal"), "{hint}");
+    let candidates = run(
+        &dir,
+        &[
+            "--regions",
+            &regions,
+            "--output-format",
+            "line",
+            "candidates",
+            "--logic",
+            &puzzle,
+        ],
+    );
+    assert_eq!(candidates.trim().len(), 81 * 9);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_sudoku_solver"))
+        .current_dir(&dir)
+        .args([
+            "--log-level",
+            "off",
+            "--regions",
+            &regions,
+            "batch",
+            &puzzle,
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "error: batch does not support irregular regions\n"
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_trace_log_is_opt_in() {
+    let dir = workdir("trace");
+    let puzzle = write(&dir, "puzzle.txt", X_PUZZLE);
+
+    run(&dir, &["count", &puzzle]);
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+    run(&dir, &["--trace-log", "trace.log", "count", &puzzle]);
+    assert!(std::fs::metadata(dir.join("trace.log")).unwrap().len() > 0);
+
+    // An unopenable log file is reported, not a panic
+    let output = Command::new(env!("CARGO_BIN_EXE_sudoku_solver"))
+        .current_dir(&dir)
+        .args(["--trace-log", "missing/trace.log", "--input-format", "line"])
+        .args(["count", &puzzle])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.starts_with("error: missing/trace.log"), "{stderr}");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}